@@ -69,7 +69,7 @@ EOF
 # Run the build
 echo "Building WASM package..."
 cd "$WASM_SRC_DIR"
-wasm-pack build --target bundler --out-dir ../pkg-wasm --features wasm
+wasm-pack build --target bundler --out-dir ../pkg-wasm --features wasm,fixed-point
 
 # Restore the README and package.json after build
 echo "Restoring README and package.json..."
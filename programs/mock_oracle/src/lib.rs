@@ -0,0 +1,63 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+declare_id!("BebwHqF7MgCSv2D9wJEUmV8ZfJuReYJjXTM1Yx2Lfm3f");
+
+/// Pyth legacy price account magic number
+pub const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+/// Pyth legacy price account version
+pub const PYTH_VERSION: u32 = 2;
+/// Pyth legacy account type for price accounts
+pub const PYTH_PRICE_ACCOUNT: u32 = 3;
+/// Pyth price status: trading
+pub const PYTH_STATUS_TRADING: u32 = 1;
+/// Bytes needed to hold the fields written by this mock
+pub const PRICE_ACCOUNT_SIZE: usize = 240;
+
+#[program]
+pub mod mock_oracle {
+    use super::*;
+
+    /// Write a price into a program-owned account using the Pyth legacy layout
+    pub fn set_price(
+        ctx: Context<SetPrice>,
+        price: i64,
+        conf: u64,
+        expo: i32,
+        publish_time: i64,
+        trading: bool,
+    ) -> Result<()> {
+        let mut data = ctx.accounts.price_account.try_borrow_mut_data()?;
+        require!(data.len() >= PRICE_ACCOUNT_SIZE, MockOracleError::AccountTooSmall);
+
+        let status = if trading { PYTH_STATUS_TRADING } else { 0 };
+
+        data[0..4].copy_from_slice(&PYTH_MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&PYTH_VERSION.to_le_bytes());
+        data[8..12].copy_from_slice(&PYTH_PRICE_ACCOUNT.to_le_bytes());
+        data[12..16].copy_from_slice(&(PRICE_ACCOUNT_SIZE as u32).to_le_bytes());
+        data[20..24].copy_from_slice(&expo.to_le_bytes());
+        data[96..104].copy_from_slice(&publish_time.to_le_bytes());
+        data[208..216].copy_from_slice(&price.to_le_bytes());
+        data[216..224].copy_from_slice(&conf.to_le_bytes());
+        data[224..228].copy_from_slice(&status.to_le_bytes());
+
+        msg!("Price set: {} +/- {} (expo {}) at {}", price, conf, expo, publish_time);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetPrice<'info> {
+    pub authority: Signer<'info>,
+
+    /// CHECK: Raw price account owned by this program, written with the Pyth layout
+    #[account(mut, owner = crate::ID)]
+    pub price_account: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum MockOracleError {
+    #[msg("Price account is too small")]
+    AccountTooSmall,
+}
@@ -0,0 +1,131 @@
+//! Deterministic Q64.64 fixed-point arithmetic
+//!
+//! All values are `u128` with 64 integer bits and 64 fractional bits.
+//! Only integer operations are used, so results are bit-identical on
+//! BPF, WASM and native targets.
+
+use anchor_lang::prelude::*;
+use crate::MathError;
+
+/// Number of fractional bits
+pub const FRAC_BITS: u32 = 64;
+
+/// 1.0 in Q64.64
+pub const ONE: u128 = 1 << FRAC_BITS;
+
+/// 0.5 in Q64.64
+pub const HALF: u128 = 1 << (FRAC_BITS - 1);
+
+/// ln(2) in Q64.64
+pub const LN_2: u128 = 0xB172_17F7_D1CF_79AB;
+
+/// Mask for the fractional part
+const FRAC_MASK: u128 = ONE - 1;
+
+/// Convert an integer to Q64.64
+pub fn from_u64(v: u64) -> u128 {
+    (v as u128) << FRAC_BITS
+}
+
+/// Multiply two Q64.64 values whose product fits in Q64.64
+/// @return None on overflow
+pub fn mul(a: u128, b: u128) -> Option<u128> {
+    let (a_hi, a_lo) = (a >> FRAC_BITS, a & FRAC_MASK);
+    let (b_hi, b_lo) = (b >> FRAC_BITS, b & FRAC_MASK);
+
+    // a * b = (a_hi*b_hi << 64) + a_hi*b_lo + a_lo*b_hi + (a_lo*b_lo >> 64)
+    let hi = a_hi.checked_mul(b_hi)?.checked_mul(ONE)?;
+    let mid = a_hi.checked_mul(b_lo)?.checked_add(a_lo.checked_mul(b_hi)?)?;
+    let lo = (a_lo * b_lo) >> FRAC_BITS;
+
+    hi.checked_add(mid)?.checked_add(lo)
+}
+
+/// Natural logarithm of the ratio `num / den`
+/// @param num Numerator (must be >= den)
+/// @param den Denominator (must be > 0)
+/// @return ln(num / den) in Q64.64
+pub fn ln_ratio(num: u128, den: u64) -> Result<u128> {
+    require!(den > 0, MathError::MathOverflow);
+    let den = den as u128;
+    require!(num >= den, MathError::InvalidBinState);
+
+    // ratio = num / den in Q64.64 (integer part must fit in 64 bits)
+    let int_part = num / den;
+    require!(int_part <= u64::MAX as u128, MathError::MathOverflow);
+    let frac_part = ((num % den) << FRAC_BITS) / den;
+    let ratio = (int_part << FRAC_BITS) | frac_part;
+
+    Ok(ln(ratio))
+}
+
+/// Natural logarithm of a Q64.64 value >= 1.0
+/// Uses ln(r) = k*ln(2) + 2*atanh((m-1)/(m+1)) with r = 2^k * m, m in [1, 2)
+pub fn ln(r: u128) -> u128 {
+    if r <= ONE {
+        return 0;
+    }
+
+    // Normalize r = 2^k * m
+    let msb = 127 - r.leading_zeros();
+    let k = msb - FRAC_BITS;
+    let m = r >> k;
+
+    // z = (m - 1) / (m + 1), z in [0, 1/3)
+    let z = ((m - ONE) << FRAC_BITS) / (m + ONE);
+    let z2 = (z * z) >> FRAC_BITS;
+
+    // atanh(z) = z + z^3/3 + z^5/5 + ...
+    let mut sum: u128 = 0;
+    let mut term = z;
+    let mut n: u128 = 1;
+    while term > 0 {
+        sum += term / n;
+        term = (term * z2) >> FRAC_BITS;
+        n += 2;
+    }
+
+    (k as u128) * LN_2 + 2 * sum
+}
+
+/// Exponential of a non-negative Q64.64 value
+/// Uses exp(x) = 2^k * exp(r) with x = k*ln(2) + r, r in [0, ln(2))
+/// @return exp(x) in Q64.64
+pub fn exp(x: u128) -> Result<u128> {
+    let k = x / LN_2;
+    // exp(x) must fit in the 64 integer bits
+    require!(k < FRAC_BITS as u128, MathError::MathOverflow);
+    let r = x - k * LN_2;
+
+    // exp(r) = 1 + r + r^2/2! + r^3/3! + ...
+    let mut sum = ONE;
+    let mut term = ONE;
+    let mut n: u128 = 1;
+    loop {
+        term = ((term * r) >> FRAC_BITS) / n;
+        if term == 0 {
+            break;
+        }
+        sum += term;
+        n += 1;
+    }
+
+    sum.checked_shl(k as u32)
+        .filter(|v| v >> k == sum)
+        .ok_or(error!(MathError::MathOverflow))
+}
+
+/// Round a Q64.64 value to the nearest integer (half up)
+pub fn round_half_up(v: u128) -> u128 {
+    (v + HALF) >> FRAC_BITS
+}
+
+/// Round a Q64.64 value up to an integer
+pub fn round_up(v: u128) -> u128 {
+    (v >> FRAC_BITS) + u128::from(v & FRAC_MASK != 0)
+}
+
+/// Round a Q64.64 value down to an integer
+pub fn round_down(v: u128) -> u128 {
+    v >> FRAC_BITS
+}
@@ -3,6 +3,9 @@ use anchor_lang::prelude::*;
 #[cfg(feature = "wasm")]
 pub mod wasm;
 
+pub mod fixed;
+pub mod ticks;
+
 #[cfg(test)]
 pub mod tests;
 
@@ -22,6 +25,61 @@ pub enum MathError {
     CanOnlySellEntireSupplyIfBinContainsAllTokens,
     #[msg("Underflow in sell calculation")]
     UnderflowInSellCalculation,
+    #[msg("Tick spacing must be positive")]
+    InvalidTickSpacing,
+    #[msg("Lower tick must not be greater than upper tick")]
+    InvalidTickRange,
+    #[msg("Tick is outside the market range")]
+    TickOutOfRange,
+    #[msg("Tick is not on the tick spacing grid")]
+    TickNotOnSpacing,
+}
+
+/// Rounding policy applied to costs and revenues
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round buy costs up and sell revenues down, so the vault never pays out dust
+    #[default]
+    ProtocolFavoring,
+    /// Round half up and return at least 1 (legacy behavior)
+    Nearest,
+}
+
+impl RoundingMode {
+    /// Round a Q64.64 buy cost to an integer
+    pub fn round_cost(self, v: u128) -> u64 {
+        let cost = match self {
+            RoundingMode::ProtocolFavoring => fixed::round_up(v),
+            RoundingMode::Nearest => fixed::round_half_up(v),
+        } as u64;
+        // Buying a positive amount always costs at least 1
+        cost.max(1)
+    }
+
+    /// Round a Q64.64 sell revenue to an integer
+    pub fn round_revenue(self, v: u128) -> u64 {
+        match self {
+            RoundingMode::ProtocolFavoring => fixed::round_down(v) as u64,
+            RoundingMode::Nearest => (fixed::round_half_up(v) as u64).max(1),
+        }
+    }
+
+    /// Round an f64 buy cost to an integer
+    pub fn round_cost_f64(self, v: f64) -> u64 {
+        let cost = match self {
+            RoundingMode::ProtocolFavoring => v.ceil() as u64,
+            RoundingMode::Nearest => (v + 0.5) as u64,
+        };
+        cost.max(1)
+    }
+
+    /// Round an f64 sell revenue to an integer
+    pub fn round_revenue_f64(self, v: f64) -> u64 {
+        match self {
+            RoundingMode::ProtocolFavoring => v.floor() as u64,
+            RoundingMode::Nearest => ((v + 0.5) as u64).max(1),
+        }
+    }
 }
 
 /// Range-Bet Math library
@@ -30,11 +88,52 @@ pub struct RangeBetMath;
 impl RangeBetMath {
     /// Token purchase cost calculation function
     /// Formula: ∫(q+t)/(T+t) dt = x + (q-T)*ln((T+x)/T)
+    /// Uses the fixed-point path when the `fixed-point` feature is enabled
     /// @param x Amount of tokens to purchase
     /// @param q Current token quantity in the bin
     /// @param t Total token quantity in the market
-    /// @return Collateral token cost
+    /// @return Collateral token cost (rounded up)
     pub fn calculate_bin_buy_cost(x: u64, q: u64, t: u64) -> Result<u64> {
+        Self::calculate_bin_buy_cost_with_rounding(x, q, t, RoundingMode::default())
+    }
+
+    /// Token purchase cost with an explicit rounding policy
+    pub fn calculate_bin_buy_cost_with_rounding(x: u64, q: u64, t: u64, rounding: RoundingMode) -> Result<u64> {
+        #[cfg(feature = "fixed-point")]
+        return Self::calculate_bin_buy_cost_fixed(x, q, t, rounding);
+        #[cfg(not(feature = "fixed-point"))]
+        return Self::calculate_bin_buy_cost_f64(x, q, t, rounding);
+    }
+
+    /// Token purchase cost calculated with Q64.64 fixed-point arithmetic
+    /// Bit-identical on every target
+    pub fn calculate_bin_buy_cost_fixed(x: u64, q: u64, t: u64, rounding: RoundingMode) -> Result<u64> {
+        // Error if q is greater than t (impossible state)
+        require!(q <= t, MathError::InvalidBinState);
+
+        if x == 0 {
+            return Ok(0);
+        }
+        if t == 0 || q == t {
+            return Ok(x); // First purchase or coefficient of log term is 0
+        }
+
+        // ln((t+x)/t)
+        let ln_ratio = fixed::ln_ratio(t as u128 + x as u128, t)?;
+
+        // x - (t-q)*ln((t+x)/t)
+        let reduction = fixed::mul(fixed::from_u64(t - q), ln_ratio);
+        match reduction {
+            Some(reduction) if reduction <= fixed::from_u64(x) => {
+                Ok(rounding.round_cost(fixed::from_u64(x) - reduction))
+            }
+            // In extreme cases, return minimum unit 1
+            _ => Ok(1),
+        }
+    }
+
+    /// Token purchase cost calculated with f64 arithmetic
+    pub fn calculate_bin_buy_cost_f64(x: u64, q: u64, t: u64, rounding: RoundingMode) -> Result<u64> {
         // Error if q is greater than t (impossible state)
         require!(q <= t, MathError::InvalidBinState);
 
@@ -75,20 +174,64 @@ impl RangeBetMath {
         if cost_f64 <= 0.0 {
             Ok(1)
         } else {
-            // Round and convert to u64
-            let cost = (cost_f64 + 0.5) as u64;
-            // Return minimum value 1 if becomes 0
-            Ok(if cost == 0 { 1 } else { cost })
+            // Round and convert to u64 (minimum value 1)
+            Ok(rounding.round_cost_f64(cost_f64))
         }
     }
     
     /// Calculate revenue from token sales
     /// Formula: ∫(q-t)/(T-t) dt = x + (q-T)*ln(T/(T-x))
+    /// Uses the fixed-point path when the `fixed-point` feature is enabled
     /// @param x Amount of tokens to sell
     /// @param q Current token quantity in the bin
     /// @param t Total token quantity in the market
-    /// @return Sale revenue
+    /// @return Sale revenue (rounded down)
     pub fn calculate_bin_sell_cost(x: u64, q: u64, t: u64) -> Result<u64> {
+        Self::calculate_bin_sell_cost_with_rounding(x, q, t, RoundingMode::default())
+    }
+
+    /// Sale revenue with an explicit rounding policy
+    pub fn calculate_bin_sell_cost_with_rounding(x: u64, q: u64, t: u64, rounding: RoundingMode) -> Result<u64> {
+        #[cfg(feature = "fixed-point")]
+        return Self::calculate_bin_sell_cost_fixed(x, q, t, rounding);
+        #[cfg(not(feature = "fixed-point"))]
+        return Self::calculate_bin_sell_cost_f64(x, q, t, rounding);
+    }
+
+    /// Sale revenue calculated with Q64.64 fixed-point arithmetic
+    /// Bit-identical on every target
+    pub fn calculate_bin_sell_cost_fixed(x: u64, q: u64, t: u64, rounding: RoundingMode) -> Result<u64> {
+        if x == 0 {
+            return Ok(0);
+        }
+
+        require!(q <= t, MathError::InvalidBinState);
+
+        // Selling the entire market supply is only possible if the bin holds all tokens
+        if x == t {
+            require!(q == t, MathError::CanOnlySellEntireSupplyIfBinContainsAllTokens);
+            return Ok(t);
+        }
+
+        require!(x <= q, MathError::CannotSellMoreThanBin);
+
+        if q == t {
+            return Ok(x);
+        }
+
+        // ln(t/(t-x))
+        let ln_ratio = fixed::ln_ratio(t as u128, t - x)?;
+
+        // x - (t-q)*ln(t/(t-x))
+        let reduction = fixed::mul(fixed::from_u64(t - q), ln_ratio)
+            .ok_or(error!(MathError::UnderflowInSellCalculation))?;
+        require!(reduction <= fixed::from_u64(x), MathError::UnderflowInSellCalculation);
+
+        Ok(rounding.round_revenue(fixed::from_u64(x) - reduction))
+    }
+
+    /// Sale revenue calculated with f64 arithmetic
+    pub fn calculate_bin_sell_cost_f64(x: u64, q: u64, t: u64, rounding: RoundingMode) -> Result<u64> {
         // 1) Edge cases
         if x == 0 {
             return Ok(0); // If sell amount is 0, return 0 revenue
@@ -137,14 +280,12 @@ impl RangeBetMath {
             // Prevent underflow - if the log term is too large compared to x, error
             require!(reduction <= revenue_f64, MathError::UnderflowInSellCalculation);
             
-            revenue_f64 = revenue_f64 - reduction;
+            revenue_f64 -= reduction;
         }
         // If q == T, the (q - T) term is 0, so revenue = x
 
         // Round and convert to u64
-        let revenue = (revenue_f64 + 0.5) as u64;
-        // Ensure minimum value of 1 when x > 0 (to prevent 0 revenue for valid sales)
-        Ok(if revenue == 0 && x > 0 { 1 } else { revenue })
+        Ok(rounding.round_revenue_f64(revenue_f64))
     }
     
     /// Calculate cost of buying tokens in multiple bins sequentially
@@ -153,6 +294,11 @@ impl RangeBetMath {
     /// @param t Total token quantity in the market
     /// @return Total cost of buying tokens in all bins
     pub fn calculate_multi_bins_buy_cost(x: u64, qs: &[u64], t: u64) -> Result<u64> {
+        Self::calculate_multi_bins_buy_cost_with_rounding(x, qs, t, RoundingMode::default())
+    }
+
+    /// Multi-bin purchase cost with an explicit rounding policy
+    pub fn calculate_multi_bins_buy_cost_with_rounding(x: u64, qs: &[u64], t: u64, rounding: RoundingMode) -> Result<u64> {
         if qs.is_empty() || x == 0 {
             return Ok(0);
         }
@@ -161,7 +307,7 @@ impl RangeBetMath {
         let mut current_t = t;
         
         for &q in qs {
-            let cost = Self::calculate_bin_buy_cost(x, q, current_t)?;
+            let cost = Self::calculate_bin_buy_cost_with_rounding(x, q, current_t, rounding)?;
             total_cost = total_cost.checked_add(cost).ok_or(error!(MathError::MathOverflow))?;
             current_t = current_t.checked_add(x).ok_or(error!(MathError::MathOverflow))?;
         }
@@ -175,6 +321,11 @@ impl RangeBetMath {
     /// @param t Total token quantity in the market
     /// @return Total revenue from selling tokens in all bins
     pub fn calculate_multi_bins_sell_cost(x: u64, qs: &[u64], t: u64) -> Result<u64> {
+        Self::calculate_multi_bins_sell_cost_with_rounding(x, qs, t, RoundingMode::default())
+    }
+
+    /// Multi-bin sale revenue with an explicit rounding policy
+    pub fn calculate_multi_bins_sell_cost_with_rounding(x: u64, qs: &[u64], t: u64, rounding: RoundingMode) -> Result<u64> {
         if qs.is_empty() || x == 0 {
             return Ok(0);
         }
@@ -191,7 +342,7 @@ impl RangeBetMath {
         let mut current_t = t;
         
         for &q in qs {
-            let revenue = Self::calculate_bin_sell_cost(x, q, current_t)?;
+            let revenue = Self::calculate_bin_sell_cost_with_rounding(x, q, current_t, rounding)?;
             total_revenue = total_revenue.checked_add(revenue).ok_or(error!(MathError::MathOverflow))?;
             current_t = current_t.checked_sub(x).ok_or(error!(MathError::MathOverflow))?;
         }
@@ -205,6 +356,11 @@ impl RangeBetMath {
     /// @param t Total token quantity in the market
     /// @return Purchasable token quantity per bin
     pub fn calculate_x_for_multi_bins(budget: u64, qs: &[u64], t: u64) -> Result<u64> {
+        Self::calculate_x_for_multi_bins_with_rounding(budget, qs, t, RoundingMode::default())
+    }
+
+    /// Budget inversion with an explicit rounding policy
+    pub fn calculate_x_for_multi_bins_with_rounding(budget: u64, qs: &[u64], t: u64, rounding: RoundingMode) -> Result<u64> {
         if budget == 0 || qs.is_empty() {
             return Ok(0);
         }
@@ -215,7 +371,7 @@ impl RangeBetMath {
         
         // Find a reasonable upper bound by doubling until we exceed budget
         loop {
-            match Self::calculate_multi_bins_buy_cost(right, qs, t) {
+            match Self::calculate_multi_bins_buy_cost_with_rounding(right, qs, t, rounding) {
                 Ok(cost) if cost <= budget => {
                     left = right;
                     if right > budget / 2 { // Prevent excessive expansion
@@ -236,7 +392,7 @@ impl RangeBetMath {
         while left + 1 < right {
             let mid = left + (right - left) / 2;
             
-            match Self::calculate_multi_bins_buy_cost(mid, qs, t) {
+            match Self::calculate_multi_bins_buy_cost_with_rounding(mid, qs, t, rounding) {
                 Ok(calculated_cost) => {
                     if calculated_cost <= budget {
                         left = mid; // mid is valid
@@ -251,7 +407,7 @@ impl RangeBetMath {
         }
         
         // Final validation - ensure the result doesn't exceed budget
-        match Self::calculate_multi_bins_buy_cost(left, qs, t) {
+        match Self::calculate_multi_bins_buy_cost_with_rounding(left, qs, t, rounding) {
             Ok(cost) if cost <= budget => Ok(left),
             _ => Ok(0),
         }
@@ -106,7 +106,7 @@ fn test_bin_buy_cost_large_dataset() {
                     Err(_) => {
                         // Error should only occur when q > t
                         // Should not happen in this test (ratio <= 1.0)
-                        assert!(false, "Calculation error: x={}, q={}, t={}", x, q, t);
+                        panic!("Calculation error: x={}, q={}, t={}", x, q, t);
                     }
                 }
             }
@@ -157,11 +157,7 @@ fn test_bin_buy_cost_mathematical_properties() {
         
         // Check if actual_cost is within 1% error margin of theoretical_cost_rounded
         let margin = (theoretical_cost_rounded / 100).max(1);
-        let diff = if actual_cost > theoretical_cost_rounded {
-            actual_cost - theoretical_cost_rounded
-        } else {
-            theoretical_cost_rounded - actual_cost
-        };
+        let diff = actual_cost.abs_diff(theoretical_cost_rounded);
         
         assert!(diff <= margin, 
             "x={}, q={}, t={}: actual={}, theoretical={}, diff={}, margin={}", 
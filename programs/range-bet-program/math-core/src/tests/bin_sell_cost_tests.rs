@@ -1,4 +1,4 @@
-use crate::RangeBetMath;
+use crate::{RangeBetMath, RoundingMode};
 
 #[test]
 fn test_bin_sell_cost_edge_cases() {
@@ -128,22 +128,26 @@ fn test_bin_sell_cost_large_dataset() {
                             
                             // As q gets closer to t, revenue gets closer to x
                             if ratio > 0.9 {
-                                assert!(revenue > x / 2);
+                                assert!(revenue >= x / 2);
                             }
                             if ratio == 1.0 {
                                 assert_eq!(revenue, x);
                             }
                             
-                            // Revenue should always be positive (when x > 0)
+                            // Revenue is rounded down, so only the nearest mode is always positive
                             if x > 0 {
-                                assert!(revenue > 0);
+                                let nearest = RangeBetMath::calculate_bin_sell_cost_with_rounding(
+                                    x, q, t, RoundingMode::Nearest
+                                ).unwrap();
+                                assert!(nearest > 0);
+                                assert!(revenue <= nearest);
                             } else {
                                 assert_eq!(revenue, 0);
                             }
                         },
                         Err(e) => {
                             // Should not have errors in this test (x <= q && x <= t)
-                            assert!(false, "Calculation error: x={}, q={}, t={}, error={:?}", x, q, t, e);
+                            panic!("Calculation error: x={}, q={}, t={}, error={:?}", x, q, t, e);
                         }
                     }
                 }
@@ -162,9 +166,13 @@ fn test_bin_sell_cost_precision() {
         // Sell from 1 lamport sequentially
         for x in 1..20 {
             let revenue = RangeBetMath::calculate_bin_sell_cost(x, q, t).unwrap();
+            let nearest = RangeBetMath::calculate_bin_sell_cost_with_rounding(
+                x, q, t, RoundingMode::Nearest
+            ).unwrap();
             
-            // Revenue should always be greater than 0
-            assert!(revenue > 0);
+            // Nearest revenue should always be greater than 0, rounded down revenue never exceeds it
+            assert!(nearest > 0);
+            assert!(revenue <= nearest);
             
             // Revenue should always be less than or equal to sale amount
             assert!(revenue <= x);
@@ -241,11 +249,7 @@ fn test_bin_sell_cost_mathematical_properties() {
         
         // Allow 1% error margin
         let margin = (theoretical_revenue_rounded / 100).max(1);
-        let diff = if actual_revenue > theoretical_revenue_rounded {
-            actual_revenue - theoretical_revenue_rounded
-        } else {
-            theoretical_revenue_rounded - actual_revenue
-        };
+        let diff = actual_revenue.abs_diff(theoretical_revenue_rounded);
         
         assert!(diff <= margin,
             "x={}, q={}, t={}: actual={}, theoretical={}, diff={}, margin={}", 
@@ -0,0 +1,152 @@
+use crate::{fixed, RangeBetMath, RoundingMode};
+use proptest::prelude::*;
+
+/// Documented error bound between the fixed-point and f64 paths:
+/// |fixed - f64| <= 1 + (t + x) / 2^50
+///
+/// The f64 path loses roughly (t + x) * 2^-52 when forming (t+x)/t and
+/// multiplying by (t - q); the fixed-point path is exact to ~2^-58.
+fn error_bound(x: u64, t: u64) -> u64 {
+    1 + ((t as u128 + x as u128) >> 50) as u64
+}
+
+#[test]
+fn test_ln_known_values() {
+    // ln(1) = 0
+    assert_eq!(fixed::ln(fixed::ONE), 0);
+
+    // ln(2) matches the constant
+    let ln2 = fixed::ln(2 * fixed::ONE);
+    assert!(ln2.abs_diff(fixed::LN_2) <= 4, "ln(2) = {:#x}", ln2);
+
+    // ln(e^k) ≈ k for small k
+    for k in 1..=40u64 {
+        let e_k = fixed::exp(fixed::from_u64(k)).unwrap();
+        let ln_e_k = fixed::ln(e_k);
+        let diff = ln_e_k.abs_diff(fixed::from_u64(k));
+        // Relative error around 2^-56
+        assert!(diff <= fixed::ONE >> 56, "k={}, diff={}", k, diff);
+    }
+}
+
+#[test]
+fn test_ln_ratio_matches_f64() {
+    let cases: [(u128, u64); 6] = [
+        (2, 1),
+        (3, 2),
+        (1_000_001, 1_000_000),
+        (10_000, 3),
+        (u64::MAX as u128, 1),
+        (u64::MAX as u128 + 1_000, u64::MAX),
+    ];
+
+    for (num, den) in cases {
+        let fixed_ln = fixed::ln_ratio(num, den).unwrap() as f64 / fixed::ONE as f64;
+        let f64_ln = (num as f64 / den as f64).ln();
+        assert!(
+            (fixed_ln - f64_ln).abs() <= 1e-12 * f64_ln.max(1.0),
+            "ln({}/{}) fixed={} f64={}", num, den, fixed_ln, f64_ln
+        );
+    }
+}
+
+#[test]
+fn test_ln_ratio_rejects_invalid_input() {
+    // ratio < 1
+    assert!(fixed::ln_ratio(1, 2).is_err());
+    // division by zero
+    assert!(fixed::ln_ratio(1, 0).is_err());
+    // integer part does not fit in 64 bits
+    assert!(fixed::ln_ratio(u64::MAX as u128 * 4, 2).is_err());
+}
+
+#[test]
+fn test_exp_overflow() {
+    // e^44 ≈ 1.28e19 still fits, e^45 does not
+    assert!(fixed::exp(fixed::from_u64(44)).is_ok());
+    assert!(fixed::exp(fixed::from_u64(45)).is_err());
+}
+
+#[test]
+fn test_fixed_edge_cases_match_f64() {
+    let cases: [(u64, u64, u64); 8] = [
+        (0, 0, 0),
+        (100, 0, 0),
+        (100, 500, 500),
+        (1, 0, 1_000_000),
+        (1, 0, u64::MAX / 1000),
+        (1_000, 500, 1_000),
+        (u64::MAX / 4, 0, u64::MAX / 2),
+        (1, 1, 2),
+    ];
+
+    for (x, q, t) in cases {
+        let fixed_cost = RangeBetMath::calculate_bin_buy_cost_fixed(x, q, t, RoundingMode::Nearest).unwrap();
+        let f64_cost = RangeBetMath::calculate_bin_buy_cost_f64(x, q, t, RoundingMode::Nearest).unwrap();
+        assert!(
+            fixed_cost.abs_diff(f64_cost) <= error_bound(x, t),
+            "buy x={}, q={}, t={}: fixed={}, f64={}", x, q, t, fixed_cost, f64_cost
+        );
+    }
+
+    // Error conditions are identical
+    assert!(RangeBetMath::calculate_bin_buy_cost_fixed(1, 2, 1, RoundingMode::Nearest).is_err());
+    assert!(RangeBetMath::calculate_bin_sell_cost_fixed(1, 2, 1, RoundingMode::Nearest).is_err());
+    assert!(RangeBetMath::calculate_bin_sell_cost_fixed(600, 500, 1_000, RoundingMode::Nearest).is_err());
+    assert!(RangeBetMath::calculate_bin_sell_cost_fixed(1_000, 500, 1_000, RoundingMode::Nearest).is_err());
+    assert_eq!(RangeBetMath::calculate_bin_sell_cost_fixed(1_000, 1_000, 1_000, RoundingMode::Nearest).unwrap(), 1_000);
+}
+
+#[test]
+fn test_fixed_is_deterministic() {
+    // Reference values computed with the fixed-point path; any change
+    // in these values changes on-chain pricing on every target.
+    assert_eq!(RangeBetMath::calculate_bin_buy_cost_fixed(100, 0, 1_000, RoundingMode::Nearest).unwrap(), 5);
+    assert_eq!(RangeBetMath::calculate_bin_buy_cost_fixed(1_000, 300, 1_000, RoundingMode::Nearest).unwrap(), 515);
+    assert_eq!(RangeBetMath::calculate_bin_sell_cost_fixed(100, 500, 1_000, RoundingMode::Nearest).unwrap(), 47);
+}
+
+proptest! {
+    #[test]
+    fn fixed_buy_cost_within_bound_of_f64(
+        x in 1u64..1_000_000_000_000u64,
+        q_ratio in 0u64..=100u64,
+        t in 1u64..1_000_000_000_000_000u64
+    ) {
+        let q = ((t as u128 * q_ratio as u128) / 100) as u64;
+
+        let fixed_cost = RangeBetMath::calculate_bin_buy_cost_fixed(x, q, t, RoundingMode::Nearest).unwrap();
+        let f64_cost = RangeBetMath::calculate_bin_buy_cost_f64(x, q, t, RoundingMode::Nearest).unwrap();
+
+        prop_assert!(
+            fixed_cost.abs_diff(f64_cost) <= error_bound(x, t),
+            "buy x={}, q={}, t={}: fixed={}, f64={}", x, q, t, fixed_cost, f64_cost
+        );
+    }
+
+    #[test]
+    fn fixed_sell_cost_within_bound_of_f64(
+        x_ratio in 1u64..=100u64,
+        q_ratio in 1u64..=100u64,
+        t in 100u64..1_000_000_000_000_000u64
+    ) {
+        let q = ((t as u128 * q_ratio as u128) / 100) as u64;
+        let x = ((q as u128 * x_ratio as u128) / 100).max(1) as u64;
+
+        let fixed_revenue = RangeBetMath::calculate_bin_sell_cost_fixed(x, q, t, RoundingMode::Nearest);
+        let f64_revenue = RangeBetMath::calculate_bin_sell_cost_f64(x, q, t, RoundingMode::Nearest);
+
+        match (fixed_revenue, f64_revenue) {
+            (Ok(fixed_revenue), Ok(f64_revenue)) => prop_assert!(
+                fixed_revenue.abs_diff(f64_revenue) <= error_bound(x, t),
+                "sell x={}, q={}, t={}: fixed={}, f64={}", x, q, t, fixed_revenue, f64_revenue
+            ),
+            // Both paths reject the same inputs
+            (Err(_), Err(_)) => {}
+            (fixed_revenue, f64_revenue) => prop_assert!(
+                false,
+                "sell x={}, q={}, t={}: fixed={:?}, f64={:?}", x, q, t, fixed_revenue, f64_revenue
+            ),
+        }
+    }
+}
@@ -8,5 +8,7 @@ pub mod edge_cases_tests;
 pub mod property_tests;
 pub mod bench;
 pub mod budget_validation_test;
+pub mod fixed_point_tests;
+pub mod tick_tests;
 
 // Common test module functions can be defined in this file. 
\ No newline at end of file
@@ -103,10 +103,8 @@ fn test_multi_bins_buy_cost_extreme_cases() {
     
     // Case where one bin is very large and others are small
     let mixed_bins = [10, 10, u64::MAX / 100000000, 10];
-    match RangeBetMath::calculate_multi_bins_buy_cost(100, &mixed_bins, 1000) {
-        Ok(_) => {},
-        Err(_) => {} // Error may occur due to extreme values
-    }
+    // Error may occur due to extreme values
+    let _ = RangeBetMath::calculate_multi_bins_buy_cost(100, &mixed_bins, 1000);
     
     // Test very large t value
     let huge_t = u64::MAX / 100;
@@ -244,7 +242,7 @@ fn test_multi_bins_buy_cost_large_dataset() {
                     },
                     Err(_) => {
                         // No errors should occur in this test
-                        assert!(false, "Calculation error: x={}, bins.len={}, t={}", x, bins.len(), t);
+                        panic!("Calculation error: x={}, bins.len={}, t={}", x, bins.len(), t);
                     }
                 }
             }
@@ -1,4 +1,4 @@
-use crate::RangeBetMath;
+use crate::{RangeBetMath, RoundingMode};
 use proptest::prelude::*;
 
 // Define test ranges
@@ -55,11 +55,7 @@ proptest! {
         
         // The difference between buy/sell price should not be too large - allow 1% tolerance
         let tolerance = (buy_cost / 100).max(1); // Minimum 1 lamport allowance
-        let difference = if buy_cost > sell_revenue {
-            buy_cost - sell_revenue
-        } else {
-            sell_revenue - buy_cost
-        };
+        let difference = buy_cost.abs_diff(sell_revenue);
         
         prop_assert!(difference <= tolerance,
             "Buy-sell roundtrip difference: buy={}, sell={}, diff={}, tolerance={}",
@@ -174,4 +170,123 @@ proptest! {
             // Ignore errors (due to overflow etc.)
         }
     }
-} 
\ No newline at end of file
+    
+    #[test]
+    fn buy_sell_roundtrip_never_profits(
+        x in 1u64..1_000_000u64,
+        q in 0u64..1_000_000u64,
+        extra_t in 0u64..1_000_000u64
+    ) {
+        let t = q + extra_t;
+        
+        // Buy x in the bin, then sell the same x back
+        let buy_cost = RangeBetMath::calculate_bin_buy_cost(x, q, t).unwrap();
+        let sell_revenue = RangeBetMath::calculate_bin_sell_cost(x, q + x, t + x).unwrap();
+        
+        prop_assert!(sell_revenue <= buy_cost,
+            "Round trip profit: x={}, q={}, t={}, buy={}, sell={}",
+            x, q, t, buy_cost, sell_revenue
+        );
+    }
+    
+    #[test]
+    fn repeated_small_roundtrips_never_profit(
+        x in 1u64..10u64,
+        q in 0u64..100_000u64,
+        extra_t in 1u64..100_000u64,
+        rounds in 1usize..50usize
+    ) {
+        let t = q + extra_t;
+        
+        // Dust-sized loops must never drain collateral
+        let mut vault: i128 = 0;
+        for _ in 0..rounds {
+            vault += RangeBetMath::calculate_bin_buy_cost(x, q, t).unwrap() as i128;
+            vault -= RangeBetMath::calculate_bin_sell_cost(x, q + x, t + x).unwrap() as i128;
+            prop_assert!(vault >= 0, "Vault drained after dust loop: {}", vault);
+        }
+    }
+    
+    #[test]
+    fn split_roundtrip_never_profits(
+        chunk in 1u64..1_000u64,
+        buy_chunks in 1u64..8u64,
+        sell_chunks in 1u64..8u64,
+        q in 0u64..100_000u64,
+        extra_t in 1u64..100_000u64
+    ) {
+        let t = q + extra_t;
+        
+        // Buy in equal chunks (each rounded separately)
+        let mut vault: i128 = 0;
+        let (mut q_cur, mut t_cur) = (q, t);
+        for _ in 0..buy_chunks {
+            vault += RangeBetMath::calculate_bin_buy_cost(chunk, q_cur, t_cur).unwrap() as i128;
+            q_cur += chunk;
+            t_cur += chunk;
+        }
+        
+        // Sell the whole position back in a different number of chunks
+        let mut remaining = chunk * buy_chunks;
+        let sell_size = remaining.div_ceil(sell_chunks);
+        while remaining > 0 {
+            let size = sell_size.min(remaining);
+            vault -= RangeBetMath::calculate_bin_sell_cost(size, q_cur, t_cur).unwrap() as i128;
+            q_cur -= size;
+            t_cur -= size;
+            remaining -= size;
+        }
+        
+        prop_assert!(vault >= 0,
+            "Split round trip profit: chunk={}, buys={}, sells={}, q={}, t={}, vault={}",
+            chunk, buy_chunks, sell_chunks, q, t, vault
+        );
+    }
+    
+    #[test]
+    fn multi_bins_roundtrip_never_profits(
+        x in 1u64..10_000u64,
+        qs in proptest::collection::vec(0u64..100_000u64, 1..5),
+        extra_t in 0u64..100_000u64
+    ) {
+        let t = qs.iter().sum::<u64>() + extra_t;
+        
+        // Buy x in every bin in order
+        let buy_cost = RangeBetMath::calculate_multi_bins_buy_cost(x, &qs, t).unwrap();
+        
+        // Sell back in reverse order, undoing each purchase exactly
+        let qs_after: Vec<u64> = qs.iter().rev().map(|&q| q + x).collect();
+        let t_after = t + x * qs.len() as u64;
+        let sell_revenue = RangeBetMath::calculate_multi_bins_sell_cost(x, &qs_after, t_after).unwrap();
+        
+        prop_assert!(sell_revenue <= buy_cost,
+            "Multi-bin round trip profit: x={}, qs={:?}, t={}, buy={}, sell={}",
+            x, qs, t, buy_cost, sell_revenue
+        );
+    }
+    
+    #[test]
+    fn protocol_favoring_rounding_brackets_nearest(
+        x in 1u64..1_000_000u64,
+        q in 1u64..1_000_000u64,
+        extra_t in 0u64..1_000_000u64
+    ) {
+        let t = q + extra_t;
+        
+        // Costs are never below the nearest rounding
+        let cost = RangeBetMath::calculate_bin_buy_cost(x, q, t).unwrap();
+        let nearest_cost = RangeBetMath::calculate_bin_buy_cost_with_rounding(
+            x, q, t, RoundingMode::Nearest
+        ).unwrap();
+        prop_assert!(cost >= nearest_cost);
+        
+        // Revenues are never above the nearest rounding
+        let sell_x = x.min(q);
+        if let Ok(revenue) = RangeBetMath::calculate_bin_sell_cost(sell_x, q, t) {
+            let nearest_revenue = RangeBetMath::calculate_bin_sell_cost_with_rounding(
+                sell_x, q, t, RoundingMode::Nearest
+            ).unwrap();
+            prop_assert!(revenue <= nearest_revenue);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{ticks, MathError};
+use anchor_lang::error::Error;
+
+fn is_math_error(err: Error, expected: MathError) -> bool {
+    err == Error::from(expected)
+}
+
+#[test]
+fn test_price_to_tick() {
+    // 123.45 with whole-unit ticks, rounding down
+    assert_eq!(ticks::price_to_tick(12345, -2, 0), 123);
+    assert_eq!(ticks::price_to_tick(-12345, -2, 0), -124);
+    assert_eq!(ticks::price_to_tick(5, 2, 0), 500);
+    assert_eq!(ticks::price_to_tick(12345, -2, -2), 12345);
+
+    // Exponents far below tick_expo floor to 0 instead of overflowing
+    assert_eq!(ticks::price_to_tick(i64::MAX, -40, 0), 0);
+    assert_eq!(ticks::price_to_tick(12345, i32::MIN, i32::MAX), 0);
+    assert_eq!(ticks::price_to_tick(-12345, -40, 0), -1);
+
+    // Exponents far above tick_expo saturate
+    assert_eq!(ticks::price_to_tick(12345, 40, 0), i64::MAX);
+    assert_eq!(ticks::price_to_tick(12345, i32::MAX, i32::MIN), i64::MAX);
+    assert_eq!(ticks::price_to_tick(-12345, 40, 0), i64::MIN);
+    assert_eq!(ticks::price_to_tick(0, 40, 0), 0);
+}
+
+#[test]
+fn test_bin_count() {
+    assert_eq!(ticks::bin_count(-360, 360, 60).unwrap(), 13);
+    assert_eq!(ticks::bin_count(0, 0, 1).unwrap(), 1);
+    assert_eq!(ticks::bin_count(-240, 960, 20).unwrap(), 61);
+
+    assert!(is_math_error(ticks::bin_count(0, 60, 0).unwrap_err(), MathError::InvalidTickSpacing));
+    assert!(is_math_error(ticks::bin_count(60, 0, 60).unwrap_err(), MathError::InvalidTickRange));
+}
+
+#[test]
+fn test_tick_to_index() {
+    assert_eq!(ticks::tick_to_index(-360, -360, 360, 60).unwrap(), 0);
+    assert_eq!(ticks::tick_to_index(0, -360, 360, 60).unwrap(), 6);
+    assert_eq!(ticks::tick_to_index(360, -360, 360, 60).unwrap(), 12);
+
+    // Off the spacing grid
+    assert!(is_math_error(
+        ticks::tick_to_index(30, -360, 360, 60).unwrap_err(),
+        MathError::TickNotOnSpacing
+    ));
+    assert!(is_math_error(
+        ticks::tick_to_index(-1, -360, 360, 60).unwrap_err(),
+        MathError::TickNotOnSpacing
+    ));
+
+    // Outside the market
+    assert!(is_math_error(
+        ticks::tick_to_index(420, -360, 360, 60).unwrap_err(),
+        MathError::TickOutOfRange
+    ));
+    assert!(is_math_error(
+        ticks::tick_to_index(-420, -360, 360, 60).unwrap_err(),
+        MathError::TickOutOfRange
+    ));
+}
+
+#[test]
+fn test_outcome_to_index() {
+    // Ticks round down to the bin containing them
+    assert_eq!(ticks::outcome_to_index(-360, -360, 360, 60, false).unwrap(), 0);
+    assert_eq!(ticks::outcome_to_index(30, -360, 360, 60, false).unwrap(), 6);
+    assert_eq!(ticks::outcome_to_index(-1, -360, 360, 60, false).unwrap(), 5);
+
+    // The last bin covers [max_tick, max_tick + tick_spacing)
+    assert_eq!(ticks::outcome_to_index(360, -360, 360, 60, false).unwrap(), 12);
+    assert_eq!(ticks::outcome_to_index(400, -360, 360, 60, false).unwrap(), 12);
+    assert_eq!(ticks::outcome_to_index(400, -360, 360, 60, true).unwrap(), 12);
+
+    // Tail bins
+    assert_eq!(ticks::outcome_to_index(-361, -360, 360, 60, true).unwrap(), 13);
+    assert_eq!(ticks::outcome_to_index(420, -360, 360, 60, true).unwrap(), 14);
+    assert!(is_math_error(
+        ticks::outcome_to_index(420, -360, 360, 60, false).unwrap_err(),
+        MathError::TickOutOfRange
+    ));
+    assert!(is_math_error(
+        ticks::outcome_to_index(-361, -360, 360, 60, false).unwrap_err(),
+        MathError::TickOutOfRange
+    ));
+
+    // Extreme ticks do not overflow
+    assert_eq!(ticks::outcome_to_index(i64::MAX, -360, 360, 60, true).unwrap(), 14);
+    assert_eq!(ticks::outcome_to_index(i64::MIN, -360, 360, 60, true).unwrap(), 13);
+}
+
+#[test]
+fn test_index_to_tick_roundtrip() {
+    for index in 0..13u16 {
+        let tick = ticks::index_to_tick(index, -360, 60).unwrap();
+        assert_eq!(ticks::tick_to_index(tick, -360, 360, 60).unwrap(), index);
+    }
+
+    assert!(is_math_error(
+        ticks::index_to_tick(u16::MAX, i64::MAX - 1, u32::MAX).unwrap_err(),
+        MathError::MathOverflow
+    ));
+}
+
+#[test]
+fn test_range_to_indices() {
+    assert_eq!(ticks::range_to_indices(-60, 60, -360, 360, 60).unwrap(), vec![5, 6, 7]);
+    assert_eq!(ticks::range_to_indices(0, 0, -360, 360, 60).unwrap(), vec![6]);
+
+    assert!(is_math_error(
+        ticks::range_to_indices(60, -60, -360, 360, 60).unwrap_err(),
+        MathError::InvalidTickRange
+    ));
+    assert!(is_math_error(
+        ticks::range_to_indices(-60, 90, -360, 360, 60).unwrap_err(),
+        MathError::TickNotOnSpacing
+    ));
+}
+
+#[test]
+fn test_extreme_ticks() {
+    // Spans beyond i64 do not overflow
+    assert!(is_math_error(
+        ticks::tick_to_index(i64::MAX, i64::MIN, i64::MAX, 1).unwrap_err(),
+        MathError::TickOutOfRange
+    ));
+    assert_eq!(ticks::tick_to_index(i64::MIN, i64::MIN, i64::MAX, 1).unwrap(), 0);
+}
@@ -67,7 +67,7 @@ fn test_calculate_x_for_multi_bins_single_bin_scenarios() {
             let actual_cost = RangeBetMath::calculate_multi_bins_buy_cost(x, &[q], t).unwrap_or(0);
             
             // Print and verify results
-            let usage_percentage = if budget > 0 { actual_cost * 100 / budget } else { 0 };
+            let usage_percentage = (actual_cost * 100).checked_div(budget).unwrap_or(0);
             println!("Actual cost for X={}: {} ({}% of budget)", x, actual_cost, usage_percentage);
             
             // Cost calculated with x should not exceed budget
@@ -77,11 +77,7 @@ fn test_calculate_x_for_multi_bins_single_bin_scenarios() {
             // Difference between budget and actual cost should be within 0.1% in Lamport units (only when x > 0)
             if x > 0 && budget >= 1000 {
                 let max_deviation = budget / 1000; // Allow 0.1% error
-                let diff = if actual_cost > budget {
-                    actual_cost - budget
-                } else {
-                    budget - actual_cost
-                };
+                let diff = actual_cost.abs_diff(budget);
                 
                 assert!(diff <= max_deviation,
                        "Difference between cost and budget ({}) exceeds allowed error ({})", diff, max_deviation);
@@ -142,7 +138,7 @@ fn test_calculate_x_for_multi_bins_multiple_bins_scenarios() {
         // Calculate cost with the calculated x
         let actual_cost = RangeBetMath::calculate_multi_bins_buy_cost(x, &bins, t).unwrap_or(0);
         println!("Actual cost for X={}: {} ({}% of budget)", 
-                x, actual_cost, if budget > 0 { actual_cost * 100 / budget } else { 0 });
+                x, actual_cost, (actual_cost * 100).checked_div(budget).unwrap_or(0));
         
         // Cost calculated with x should not exceed budget
         assert!(actual_cost <= budget);
@@ -150,11 +146,7 @@ fn test_calculate_x_for_multi_bins_multiple_bins_scenarios() {
         // Difference between budget and actual cost should be within 0.1% in lamport units (only when x > 0)
         if x > 0 && budget >= 1000 {
             let acceptable_deviation = (budget / 1000).max(1); // Allow 0.1% error, minimum 1 lamport
-            let diff = if actual_cost > budget {
-                actual_cost - budget
-            } else {
-                budget - actual_cost
-            };
+            let diff = actual_cost.abs_diff(budget);
             assert!(diff <= acceptable_deviation,
                    "Difference between cost and budget ({}) exceeds allowed error ({})", diff, acceptable_deviation);
         }
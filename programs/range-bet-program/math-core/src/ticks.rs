@@ -0,0 +1,94 @@
+//! Conversion between prices, tick values and bin indices
+//!
+//! A market covers ticks `min_tick..=max_tick` on a grid of `tick_spacing`.
+//! Bin `i` is identified by its lower tick `min_tick + i * tick_spacing`.
+
+use anchor_lang::prelude::*;
+use crate::MathError;
+
+/// Convert a price to a tick, rounding down (tick = price * 10^(expo - tick_expo))
+///
+/// Ticks beyond the `i64` range saturate, so extreme exponents still land in a tail bin.
+pub fn price_to_tick(price: i64, expo: i32, tick_expo: i32) -> i64 {
+    let shift = expo as i64 - tick_expo as i64;
+    let scale = 10i64.checked_pow(shift.unsigned_abs() as u32);
+
+    if shift >= 0 {
+        price.saturating_mul(scale.unwrap_or(i64::MAX))
+    } else {
+        // A divisor beyond i64 exceeds any price, which floors to 0 (or -1 when negative)
+        scale.map_or(if price < 0 { -1 } else { 0 }, |scale| price.div_euclid(scale))
+    }
+}
+
+/// Number of bins in a market
+pub fn bin_count(min_tick: i64, max_tick: i64, tick_spacing: u32) -> Result<usize> {
+    require!(tick_spacing > 0, MathError::InvalidTickSpacing);
+    require!(min_tick <= max_tick, MathError::InvalidTickRange);
+
+    let span = (max_tick as i128) - (min_tick as i128);
+    Ok((span / tick_spacing as i128 + 1) as usize)
+}
+
+/// Bin index of a tick on the spacing grid
+pub fn tick_to_index(tick: i64, min_tick: i64, max_tick: i64, tick_spacing: u32) -> Result<u16> {
+    require!(tick_spacing > 0, MathError::InvalidTickSpacing);
+    require!(tick >= min_tick && tick <= max_tick, MathError::TickOutOfRange);
+
+    let offset = (tick as i128) - (min_tick as i128);
+    require!(offset % tick_spacing as i128 == 0, MathError::TickNotOnSpacing);
+
+    u16::try_from(offset / tick_spacing as i128).map_err(|_| error!(MathError::TickOutOfRange))
+}
+
+/// Bin containing an outcome tick
+///
+/// Bin `i` covers `[min_tick + i * tick_spacing, min_tick + (i + 1) * tick_spacing)`, so ticks off the
+/// grid round down. With `tail_bins`, ticks below `min_tick` go to the underflow bin (index `n`) and
+/// ticks from `max_tick + tick_spacing` up to the overflow bin (index `n + 1`), `n` being `bin_count`.
+pub fn outcome_to_index(
+    tick: i64,
+    min_tick: i64,
+    max_tick: i64,
+    tick_spacing: u32,
+    tail_bins: bool,
+) -> Result<u16> {
+    let range_bin_count = bin_count(min_tick, max_tick, tick_spacing)? as i128;
+
+    let offset = (tick as i128) - (min_tick as i128);
+    let index = if offset < 0 {
+        require!(tail_bins, MathError::TickOutOfRange);
+        range_bin_count
+    } else if offset / tick_spacing as i128 >= range_bin_count {
+        require!(tail_bins, MathError::TickOutOfRange);
+        range_bin_count + 1
+    } else {
+        offset / tick_spacing as i128
+    };
+
+    u16::try_from(index).map_err(|_| error!(MathError::TickOutOfRange))
+}
+
+/// Lower tick of a bin
+pub fn index_to_tick(index: u16, min_tick: i64, tick_spacing: u32) -> Result<i64> {
+    (index as i64)
+        .checked_mul(tick_spacing as i64)
+        .and_then(|offset| min_tick.checked_add(offset))
+        .ok_or(error!(MathError::MathOverflow))
+}
+
+/// Bin indices for the ticks `lower_tick..=upper_tick`
+pub fn range_to_indices(
+    lower_tick: i64,
+    upper_tick: i64,
+    min_tick: i64,
+    max_tick: i64,
+    tick_spacing: u32,
+) -> Result<Vec<u16>> {
+    require!(lower_tick <= upper_tick, MathError::InvalidTickRange);
+
+    let lower = tick_to_index(lower_tick, min_tick, max_tick, tick_spacing)?;
+    let upper = tick_to_index(upper_tick, min_tick, max_tick, tick_spacing)?;
+
+    Ok((lower..=upper).collect())
+}
@@ -1,6 +1,7 @@
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 use super::RangeBetMath;
+use super::ticks;
 
 // 1-bin buy
 #[cfg(feature = "wasm")]
@@ -37,6 +38,58 @@ pub fn calculate_x_for_multi_bins(budget: u64, qs: Vec<u64>, t: u64) -> u64 {
     RangeBetMath::calculate_x_for_multi_bins(budget, &qs, t).unwrap()
 } 
 
+// number of bins in a market
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = binCount)]
+pub fn bin_count(min_tick: i64, max_tick: i64, tick_spacing: u32) -> Result<usize, String> {
+    ticks::bin_count(min_tick, max_tick, tick_spacing)
+        .map_err(|e| format!("Tick error: {:?}", e))
+}
+
+// tick -> bin index
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = tickToIndex)]
+pub fn tick_to_index(tick: i64, min_tick: i64, max_tick: i64, tick_spacing: u32) -> Result<u16, String> {
+    ticks::tick_to_index(tick, min_tick, max_tick, tick_spacing)
+        .map_err(|e| format!("Tick error: {:?}", e))
+}
+
+// outcome tick -> bin index (tail bins included)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = outcomeToIndex)]
+pub fn outcome_to_index(
+    tick: i64,
+    min_tick: i64,
+    max_tick: i64,
+    tick_spacing: u32,
+    tail_bins: bool,
+) -> Result<u16, String> {
+    ticks::outcome_to_index(tick, min_tick, max_tick, tick_spacing, tail_bins)
+        .map_err(|e| format!("Tick error: {:?}", e))
+}
+
+// bin index -> lower tick
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = indexToTick)]
+pub fn index_to_tick(index: u16, min_tick: i64, tick_spacing: u32) -> Result<i64, String> {
+    ticks::index_to_tick(index, min_tick, tick_spacing)
+        .map_err(|e| format!("Tick error: {:?}", e))
+}
+
+// tick range -> bin indices
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = rangeToIndices)]
+pub fn range_to_indices(
+    lower_tick: i64,
+    upper_tick: i64,
+    min_tick: i64,
+    max_tick: i64,
+    tick_spacing: u32,
+) -> Result<Vec<u16>, String> {
+    ticks::range_to_indices(lower_tick, upper_tick, min_tick, max_tick, tick_spacing)
+        .map_err(|e| format!("Tick error: {:?}", e))
+}
+
 // ===== EVM uint256 compatible interfaces (using strings) =====
 
 #[cfg(feature = "wasm")]
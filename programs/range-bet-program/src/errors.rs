@@ -68,7 +68,7 @@ pub enum RangeBetError {
     #[msg("Owner only function")]
     OwnerOnly,
     
-    #[msg("Markets must be closed in sequential order")]
+    #[msg("Previous market in the series must be resolved first")]
     IncorrectMarketOrderForClosing,
     
     #[msg("Cannot transfer to self")]
@@ -79,4 +79,190 @@ pub enum RangeBetError {
     
     #[msg("Bin token quantity cannot be greater than total token quantity")]
     InvalidBinState,
-} 
\ No newline at end of file
+    
+    #[msg("Must sell at least one bin")]
+    NoTokensToSell,
+    
+    #[msg("Cannot sell more tokens than held in position")]
+    InsufficientTokensToSell,
+    
+    #[msg("Revenue is below minimum collateral out")]
+    RevenueBelowMinCollateral,
+    
+    #[msg("Purchasable token quantity is below minimum")]
+    TokensBelowMinimum,
+    
+    #[msg("Refunds are still outstanding")]
+    RefundsOutstanding,
+    
+    #[msg("Next market accounts are required to roll over the pool")]
+    MissingNextMarket,
+    
+    #[msg("Invalid vault account")]
+    InvalidVault,
+    
+    #[msg("Invalid collateral mint")]
+    InvalidCollateralMint,
+    
+    #[msg("Market is voided")]
+    MarketVoided,
+    
+    #[msg("Market is not voided")]
+    MarketNotVoided,
+    
+    #[msg("Refund deadline must be in the future")]
+    InvalidRefundDeadline,
+    
+    #[msg("Refund period has ended")]
+    RefundPeriodEnded,
+    
+    #[msg("No refund available for this position")]
+    NoRefundAvailable,
+    
+    #[msg("Close time must be in the future")]
+    InvalidCloseTime,
+    
+    #[msg("Trading cutoff must be non-negative and end trading after now")]
+    InvalidTradingCutoff,
+    
+    #[msg("Trading period has ended")]
+    TradingClosed,
+    
+    #[msg("Market cannot be resolved before its close time")]
+    CloseTimeNotReached,
+    
+    #[msg("New close time must be later than the current close time")]
+    CloseTimeNotExtended,
+    
+    #[msg("Series previous market must be an existing market")]
+    InvalidSeriesMarket,
+    
+    #[msg("Invalid oracle configuration")]
+    InvalidOracleConfig,
+    
+    #[msg("Market is not configured for oracle resolution")]
+    OracleNotConfigured,
+    
+    #[msg("Invalid oracle price account")]
+    InvalidOracleAccount,
+    
+    #[msg("Oracle price is not available")]
+    OraclePriceUnavailable,
+    
+    #[msg("Oracle price was not published within the resolution window")]
+    OraclePriceOutsideWindow,
+    
+    #[msg("Oracle price confidence interval is too wide")]
+    OracleConfidenceTooWide,
+    
+    #[msg("Oracle price is outside the market range")]
+    OraclePriceOutOfRange,
+    
+    #[msg("Dispute window must be non-negative")]
+    InvalidDisputeWindow,
+    
+    #[msg("Only the owner or the resolver can perform this action")]
+    ResolverOnly,
+    
+    #[msg("A resolution has already been proposed")]
+    ResolutionAlreadyProposed,
+    
+    #[msg("No resolution has been proposed")]
+    NoResolutionProposed,
+    
+    #[msg("Challenge window has ended")]
+    ChallengeWindowClosed,
+    
+    #[msg("Challenge window is still open")]
+    ChallengeWindowOpen,
+    
+    #[msg("Market has more bins than can be indexed")]
+    TooManyBins,
+    
+    #[msg("Market bins are not fully allocated")]
+    BinsNotAllocated,
+    
+    #[msg("All market bins are already allocated")]
+    BinsAlreadyAllocated,
+    
+    #[msg("Position still has a payout or refund to claim")]
+    PositionHasClaim,
+    
+    #[msg("Market account does not match the position")]
+    InvalidMarketAccount,
+    
+    #[msg("Must close at least one position")]
+    NoPositionsToClose,
+    
+    #[msg("Market still has unclaimed payouts or refunds")]
+    MarketNotSettled,
+    
+    #[msg("No-winner pool must be rolled over before the market is finalized")]
+    RollOverPending,
+    
+    #[msg("Creator account does not match the market")]
+    InvalidCreator,
+    
+    #[msg("Claim window must not be negative")]
+    InvalidClaimWindow,
+    
+    #[msg("Claim deadline has passed")]
+    ClaimDeadlinePassed,
+    
+    #[msg("Winnings are still unclaimed")]
+    ClaimsOutstanding,
+    
+    #[msg("Fee exceeds the maximum")]
+    InvalidFeeBps,
+    
+    #[msg("Only the owner or the treasurer can perform this action")]
+    TreasurerOnly,
+    
+    #[msg("No fees to collect")]
+    NoFeesToCollect,
+    
+    #[msg("Only the owner or the market creator can perform this action")]
+    MarketCreatorOnly,
+    
+    #[msg("Only the owner or the pauser can perform this action")]
+    PauserOnly,
+    
+    #[msg("No ownership transfer is pending")]
+    NoPendingOwner,
+    
+    #[msg("Only the pending owner can accept ownership")]
+    PendingOwnerOnly,
+    
+    #[msg("Invalid new owner")]
+    InvalidNewOwner,
+    
+    #[msg("Program is paused")]
+    ProgramPaused,
+    
+    #[msg("Claims are paused")]
+    ClaimsPaused,
+    
+    #[msg("Creator fee share exceeds 100%")]
+    InvalidCreatorFeeBps,
+    
+    #[msg("Creator token account is required")]
+    MissingCreatorTokenAccount,
+    
+    #[msg("Market has no bond to settle")]
+    NoBondToSettle,
+    
+    #[msg("Creator bond must be settled before the market is finalized")]
+    BondNotSettled,
+    
+    #[msg("Fees must be collected before the market is finalized")]
+    FeesNotCollected,
+    
+    #[msg("Only the market creator role can configure an oracle")]
+    OracleCreatorOnly,
+    
+    #[msg("Dispute window is below the minimum for bonded markets")]
+    DisputeWindowTooShort,
+    
+    #[msg("Claim window is below the minimum for bonded markets")]
+    ClaimWindowTooShort,
+}
\ No newline at end of file
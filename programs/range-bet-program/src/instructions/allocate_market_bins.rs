@@ -0,0 +1,104 @@
+use anchor_lang::{prelude::*, system_program};
+use crate::state::{ProgramState, Market, MarketBins, MarketBinsAllocated, Role};
+use crate::errors::RangeBetError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct AllocateMarketBins<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Bonded creators allocate their own market's bins
+    #[account(
+        seeds = [b"market", &market_id.to_le_bytes()],
+        bump,
+        constraint = !market.closed @ RangeBetError::MarketClosed,
+        constraint = market.creator == authority.key()
+            || program_state.has_role(Role::MarketCreator, &authority.key()) @ RangeBetError::MarketCreatorOnly
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Market bin storage to grow
+    #[account(
+        mut,
+        seeds = [b"bins", &market_id.to_le_bytes()],
+        bump
+    )]
+    pub market_bins: AccountLoader<'info, MarketBins>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn allocate_market_bins(ctx: Context<AllocateMarketBins>, market_id: u64) -> Result<()> {
+    let bin_count = ctx.accounts.market.bin_count;
+
+    let allocated = allocate_bins(
+        &ctx.accounts.market_bins,
+        market_id,
+        bin_count,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+    )?;
+
+    msg!("Market bins allocated: ID = {}, {}/{}", market_id, allocated, bin_count);
+
+    Ok(())
+}
+
+/// Grow `market_bins` by up to `MAX_BINS_PER_ALLOCATION` bins towards `bin_count`, with `payer` covering rent
+/// @return Number of bins allocated after this step
+pub(crate) fn allocate_bins<'info>(
+    market_bins: &AccountLoader<'info, MarketBins>,
+    market_id: u64,
+    bin_count: u32,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<u32> {
+    let bins_ai = market_bins.to_account_info();
+
+    let current = MarketBins::allocated(&bins_ai.try_borrow_data()?);
+    require!(current < bin_count as usize, RangeBetError::BinsAlreadyAllocated);
+
+    // Each step stays under the realloc limit
+    let allocated = (current + MarketBins::MAX_BINS_PER_ALLOCATION).min(bin_count as usize);
+    let new_size = MarketBins::space(allocated);
+
+    // Calculate needed lamports
+    let needed_lamports = Rent::get()?.minimum_balance(new_size);
+
+    // If current account's lamports are insufficient, transfer additional lamports
+    if needed_lamports > bins_ai.lamports() {
+        let diff = needed_lamports - bins_ai.lamports();
+
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: bins_ai.clone(),
+                },
+            ),
+            diff,
+        )?;
+    }
+
+    // Perform realloc (new bins start at zero)
+    bins_ai.realloc(new_size, false)?;
+    MarketBins::set_allocated(&mut bins_ai.try_borrow_mut_data()?, allocated as u32);
+
+    // Emit event
+    emit!(MarketBinsAllocated {
+        market_id,
+        allocated: allocated as u32,
+        bin_count,
+    });
+
+    Ok(allocated as u32)
+}
+
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, TokenAccount, Token, Transfer};
-use crate::state::{Market, UserMarketPosition, BinBal, TokensBought};
+use crate::state::{ProgramState, Market, MarketBins, UserMarketPosition, BinBal, TokensBought};
 use crate::errors::RangeBetError;
+use crate::position;
 pub use range_bet_math_core::RangeBetMath;
+use range_bet_math_core::ticks;
 
 #[derive(Accounts)]
 #[instruction(market_id: u64)]
@@ -10,6 +12,14 @@ pub struct BuyTokens<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     
+    /// Program state (global pause)
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = !program_state.trading_paused() @ RangeBetError::ProgramPaused
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
     /// Market account
     #[account(
         mut,
@@ -20,24 +30,35 @@ pub struct BuyTokens<'info> {
     )]
     pub market: Account<'info, Market>,
     
-    /// User position
+    /// Market bin quantities
+    #[account(
+        mut,
+        seeds = [b"bins", &market_id.to_le_bytes()],
+        bump
+    )]
+    pub market_bins: AccountLoader<'info, MarketBins>,
+    
+    /// User position (created if it doesn't exist, resized to fit its bins)
     #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + std::mem::size_of::<UserMarketPosition>() + 16 * 100, // Reserve space for 100 bins
+        mut,
         seeds = [b"pos", user.key().as_ref(), &market_id.to_le_bytes()],
         bump
     )]
-    pub user_position: Account<'info, UserMarketPosition>,
+    /// CHECK: Created or deserialized as a UserMarketPosition by the position helpers
+    pub user_position: UncheckedAccount<'info>,
     
     /// User token account
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint
+    )]
     pub user_token_account: Account<'info, TokenAccount>,
     
     /// Market Vault account
     #[account(
         mut,
-        constraint = vault.mint == user_token_account.mint
+        address = market.vault @ RangeBetError::InvalidVault,
+        constraint = vault.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint
     )]
     pub vault: Account<'info, TokenAccount>,
     
@@ -55,18 +76,113 @@ pub fn buy_tokens(
 ) -> Result<()> {
     // Validation
     require!(bin_indices.len() == amounts.len(), RangeBetError::ArrayLengthMismatch);
-    require!(bin_indices.len() > 0, RangeBetError::NoTokensToBuy);
+    require!(!bin_indices.is_empty(), RangeBetError::NoTokensToBuy);
     
-    let market = &mut ctx.accounts.market;
-    let user_position = &mut ctx.accounts.user_position;
+    // Trading stops at close_ts minus the trading cutoff
+    require!(
+        ctx.accounts.market.is_trading_open(Clock::get()?.unix_timestamp),
+        RangeBetError::TradingClosed
+    );
+    
+    let user_ai = ctx.accounts.user.to_account_info();
+    let system_program_ai = ctx.accounts.system_program.to_account_info();
+    let position_ai = ctx.accounts.user_position.to_account_info();
     
     // Initialize if needed
-    if user_position.owner == Pubkey::default() {
-        user_position.owner = ctx.accounts.user.key();
-        // Use market_id directly from parameters
-        user_position.market_id = market_id;
-        user_position.bins = Vec::new();
-    }
+    let mut user_position = position::load_or_create(
+        &position_ai,
+        ctx.accounts.user.key(),
+        market_id,
+        ctx.bumps.user_position,
+        &user_ai,
+        &system_program_ai,
+    )?;
+    
+    let market = &mut ctx.accounts.market;
+    let total_cost = apply_buy(market, &ctx.accounts.market_bins, &mut user_position, &bin_indices, &amounts)?;
+    
+    // Protocol fee is paid on top of the cost
+    let fee = market.fee_for(total_cost);
+    let total_paid = total_cost.checked_add(fee).ok_or(error!(RangeBetError::MathOverflow))?;
+    
+    // Check if cost exceeds maximum collateral
+    require!(total_paid <= max_collateral, RangeBetError::CostExceedsMaxCollateral);
+    
+    // Transfer tokens
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+    );
+    
+    token::transfer(cpi_ctx, total_paid)?;
+    
+    // Update market state
+    market.collateral_balance = market.collateral_balance.checked_add(total_cost)
+        .ok_or(error!(RangeBetError::MathOverflow))?;
+    market.fees_accrued = market.fees_accrued.checked_add(fee)
+        .ok_or(error!(RangeBetError::MathOverflow))?;
+    
+    // Grow the position to fit new bins (user pays the rent difference)
+    position::store(&position_ai, &mut user_position, &user_ai, &system_program_ai)?;
+    
+    // Emit event
+    emit!(TokensBought {
+        market_id: user_position.market_id,
+        buyer: ctx.accounts.user.key(),
+        total_cost,
+        fee,
+    });
+    
+    msg!("Token purchase complete: Market ID = {}, Buyer = {}, Cost = {}, Fee = {}", 
+        user_position.market_id, 
+        ctx.accounts.user.key(), 
+        total_cost,
+        fee
+    );
+    
+    Ok(())
+}
+
+/// Buy `amount_per_bin` tokens in every bin from `lower_tick` to `upper_tick` (inclusive)
+pub fn buy_range(
+    ctx: Context<BuyTokens>,
+    market_id: u64,
+    lower_tick: i64,
+    upper_tick: i64,
+    amount_per_bin: u64,
+    max_collateral: u64,
+) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let bin_indices = ticks::range_to_indices(
+        lower_tick,
+        upper_tick,
+        market.min_tick,
+        market.max_tick,
+        market.tick_spacing,
+    )?;
+    let amounts = vec![amount_per_bin; bin_indices.len()];
+    
+    buy_tokens(ctx, market_id, bin_indices, amounts, max_collateral)
+}
+
+/// Adds `amounts` to the given bins of the market and the user position
+/// @return Total collateral cost of the purchase
+pub(crate) fn apply_buy(
+    market: &mut Market,
+    market_bins: &AccountLoader<MarketBins>,
+    user_position: &mut UserMarketPosition,
+    bin_indices: &[u16],
+    amounts: &[u64],
+) -> Result<u64> {
+    let bins_ai = market_bins.to_account_info();
+    let mut bins = bins_ai.try_borrow_mut_data()?;
+    MarketBins::require_allocated(&bins, market.bin_count)?;
     
     let mut t_current = market.t_total;
     let mut total_cost: u64 = 0;
@@ -81,14 +197,11 @@ pub fn buy_tokens(
             continue;
         }
         
-        // Check array index range
-        require!(index < market.bins.len() as u16, RangeBetError::BinIndexOutOfRange);
-        
-        // Get quantity from market bin
-        let bin_q = market.bins[index as usize];
+        // Get quantity from market bin (checks the index range)
+        let bin_q = MarketBins::get(&bins, index)?;
         
         // Update market bin quantity
-        market.bins[index as usize] += amount;
+        MarketBins::set(&mut bins, index, bin_q + amount)?;
         
         // Calculate cost
         let cost = RangeBetMath::calculate_bin_buy_cost(amount, bin_q, t_current)?;
@@ -100,6 +213,7 @@ pub fn buy_tokens(
         for bin_bal in &mut user_position.bins {
             if bin_bal.index == index {
                 bin_bal.amount += amount;
+                bin_bal.paid = bin_bal.paid.checked_add(cost).ok_or(error!(RangeBetError::MathOverflow))?;
                 user_bin_found = true;
                 break;
             }
@@ -110,6 +224,8 @@ pub fn buy_tokens(
             user_position.bins.push(BinBal {
                 index,
                 amount,
+                paid: cost,
+                received: 0,
             });
         }
         
@@ -117,40 +233,14 @@ pub fn buy_tokens(
         t_current += amount;
     }
     
-    // Check if cost exceeds maximum collateral
-    require!(total_cost <= max_collateral, RangeBetError::CostExceedsMaxCollateral);
-    
-    // Transfer tokens
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.user_token_account.to_account_info(),
-        to: ctx.accounts.vault.to_account_info(),
-        authority: ctx.accounts.user.to_account_info(),
-    };
-    
-    let cpi_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-    );
-    
-    token::transfer(cpi_ctx, total_cost)?;
-    
     // Update market state
     market.t_total = t_current;
-    market.collateral_balance = market.collateral_balance.checked_add(total_cost)
-        .ok_or(error!(RangeBetError::MathOverflow))?;
-    
-    // Emit event
-    emit!(TokensBought {
-        market_id: user_position.market_id,
-        buyer: ctx.accounts.user.key(),
-        total_cost,
-    });
     
-    msg!("Token purchase complete: Market ID = {}, Buyer = {}, Cost = {}", 
-        user_position.market_id, 
-        ctx.accounts.user.key(), 
-        total_cost
-    );
+    // Track cost basis
+    user_position.cost_basis = user_position.cost_basis.checked_add(total_cost)
+        .ok_or(error!(RangeBetError::MathOverflow))?;
+    market.total_cost_basis = market.total_cost_basis.checked_add(total_cost)
+        .ok_or(error!(RangeBetError::MathOverflow))?;
     
-    Ok(())
-} 
\ No newline at end of file
+    Ok(total_cost)
+}
@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount, Token, Transfer};
+use crate::state::{ProgramState, Market, MarketBins, TokensBought};
+use crate::errors::RangeBetError;
+use crate::instructions::buy_tokens::apply_buy;
+use crate::position;
+use range_bet_math_core::RangeBetMath;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct BuyWithBudget<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Program state (global pause)
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = !program_state.trading_paused() @ RangeBetError::ProgramPaused
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Market account
+    #[account(
+        mut,
+        seeds = [b"market", &market_id.to_le_bytes()],
+        bump,
+        constraint = market.active @ RangeBetError::MarketNotActive,
+        constraint = !market.closed @ RangeBetError::MarketClosed
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Market bin quantities
+    #[account(
+        mut,
+        seeds = [b"bins", &market_id.to_le_bytes()],
+        bump
+    )]
+    pub market_bins: AccountLoader<'info, MarketBins>,
+
+    /// User position (created if it doesn't exist, resized to fit its bins)
+    #[account(
+        mut,
+        seeds = [b"pos", user.key().as_ref(), &market_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: Created or deserialized as a UserMarketPosition by the position helpers
+    pub user_position: UncheckedAccount<'info>,
+
+    /// User token account
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// Market Vault account
+    #[account(
+        mut,
+        address = market.vault @ RangeBetError::InvalidVault,
+        constraint = vault.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn buy_with_budget(
+    ctx: Context<BuyWithBudget>,
+    market_id: u64,
+    bin_indices: Vec<u16>,
+    budget: u64,
+    min_tokens_per_bin: u64,
+) -> Result<()> {
+    // Validation
+    require!(!bin_indices.is_empty(), RangeBetError::NoTokensToBuy);
+
+    // Trading stops at close_ts minus the trading cutoff
+    require!(
+        ctx.accounts.market.is_trading_open(Clock::get()?.unix_timestamp),
+        RangeBetError::TradingClosed
+    );
+
+    let user_ai = ctx.accounts.user.to_account_info();
+    let system_program_ai = ctx.accounts.system_program.to_account_info();
+    let position_ai = ctx.accounts.user_position.to_account_info();
+
+    // Initialize if needed
+    let mut user_position = position::load_or_create(
+        &position_ai,
+        ctx.accounts.user.key(),
+        market_id,
+        ctx.bumps.user_position,
+        &user_ai,
+        &system_program_ai,
+    )?;
+
+    let market = &mut ctx.accounts.market;
+
+    // Collect live bin quantities
+    let mut qs = Vec::with_capacity(bin_indices.len());
+    {
+        let bins_ai = ctx.accounts.market_bins.to_account_info();
+        let bins = bins_ai.try_borrow_data()?;
+        MarketBins::require_allocated(&bins, market.bin_count)?;
+        for &index in &bin_indices {
+            qs.push(MarketBins::get(&bins, index)?);
+        }
+    }
+
+    // Budget covers the protocol fee on top of the cost
+    let cost_budget = (budget as u128 * 10_000 / (10_000 + market.fee_bps as u128)) as u64;
+
+    // Calculate per-bin quantity purchasable with the budget
+    let amount = RangeBetMath::calculate_x_for_multi_bins(cost_budget, &qs, market.t_total)?;
+    require!(amount > 0, RangeBetError::NoTokensToBuy);
+    require!(amount >= min_tokens_per_bin, RangeBetError::TokensBelowMinimum);
+
+    let amounts = vec![amount; bin_indices.len()];
+    let total_cost = apply_buy(market, &ctx.accounts.market_bins, &mut user_position, &bin_indices, &amounts)?;
+    let fee = market.fee_for(total_cost);
+    let total_paid = total_cost.checked_add(fee).ok_or(error!(RangeBetError::MathOverflow))?;
+
+    // Check if cost exceeds budget
+    require!(total_paid <= budget, RangeBetError::CostExceedsMaxCollateral);
+
+    // Transfer tokens
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+    );
+
+    token::transfer(cpi_ctx, total_paid)?;
+
+    // Update market state
+    market.collateral_balance = market.collateral_balance.checked_add(total_cost)
+        .ok_or(error!(RangeBetError::MathOverflow))?;
+    market.fees_accrued = market.fees_accrued.checked_add(fee)
+        .ok_or(error!(RangeBetError::MathOverflow))?;
+
+    // Grow the position to fit new bins (user pays the rent difference)
+    position::store(&position_ai, &mut user_position, &user_ai, &system_program_ai)?;
+
+    // Emit event
+    emit!(TokensBought {
+        market_id,
+        buyer: ctx.accounts.user.key(),
+        total_cost,
+        fee,
+    });
+
+    msg!("Budget purchase complete: Market ID = {}, Buyer = {}, Tokens per bin = {}, Cost = {}, Fee = {}",
+        market_id,
+        ctx.accounts.user.key(),
+        amount,
+        total_cost,
+        fee
+    );
+
+    Ok(())
+}
@@ -1,13 +1,22 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, TokenAccount, Token, Transfer};
-use crate::state::{Market, UserMarketPosition, RewardClaimed};
+use crate::state::{ProgramState, Market, UserMarketPosition, RewardClaimed, NoWinnerPolicy};
 use crate::errors::RangeBetError;
+use crate::position;
 
 #[derive(Accounts)]
 pub struct ClaimReward<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     
+    /// Program state (global pause)
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = !program_state.claims_paused() @ RangeBetError::ClaimsPaused
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
     #[account(
         mut,
         seeds = [b"market", &user_position.market_id.to_le_bytes()],
@@ -24,12 +33,17 @@ pub struct ClaimReward<'info> {
     )]
     pub user_position: Account<'info, UserMarketPosition>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint
+    )]
     pub user_token_account: Account<'info, TokenAccount>,
     
     #[account(
         mut,
-        constraint = vault.mint == user_token_account.mint
+        address = market.vault @ RangeBetError::InvalidVault,
+        constraint = vault.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint,
+        constraint = vault.owner == vault_authority.key() @ RangeBetError::InvalidVault
     )]
     pub vault: Account<'info, TokenAccount>,
     
@@ -52,37 +66,42 @@ pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
     // Check if market is closed
     require!(market.closed, RangeBetError::MarketIsNotClosed);
     
+    // Voided markets are settled through refund_position
+    require!(!market.voided, RangeBetError::MarketVoided);
+    
+    // Unclaimed payouts may have been swept after the deadline
+    require!(
+        !market.claims_expired(Clock::get()?.unix_timestamp),
+        RangeBetError::ClaimDeadlinePassed
+    );
+    
     // Check if winning bin is set
     let winning_bin = market.winning_bin.ok_or(error!(RangeBetError::BinIndexOutOfRange))?;
     
+    // No winner with refunds: claim against cost basis instead of winning tokens
+    let is_refund = market.has_no_winner() && market.no_winner_policy == NoWinnerPolicy::Refund;
+    
     // Find tokens bet on the winning bin
     let mut user_winning_amount = 0;
     let mut user_bin_index = None;
     
-    for (i, bin_bal) in user_position.bins.iter().enumerate() {
-        if bin_bal.index == winning_bin && bin_bal.amount > 0 {
-            user_winning_amount = bin_bal.amount;
-            user_bin_index = Some(i);
-            break;
+    if is_refund {
+        user_winning_amount = user_position.cost_basis;
+    } else {
+        for (i, bin_bal) in user_position.bins.iter().enumerate() {
+            if bin_bal.index == winning_bin && bin_bal.amount > 0 {
+                user_winning_amount = bin_bal.amount;
+                user_bin_index = Some(i);
+                break;
+            }
         }
     }
     
     // Check if there are tokens to claim from the winning bin
     require!(user_winning_amount > 0, RangeBetError::NotWinningBin);
     
-    // Get total token amount for the winning bin
-    let total_winning_tokens = market.bins[winning_bin as usize];
-    
-    // Calculate reward: (user token amount / total winning tokens) * total collateral balance
-    let reward_amount = (user_winning_amount as u128 * market.collateral_balance as u128) 
-        / total_winning_tokens as u128;
-    
-    // Check u64 range
-    let reward_amount = if reward_amount > u64::MAX as u128 {
-        u64::MAX
-    } else {
-        reward_amount as u64
-    };
+    // Calculate reward from the snapshot taken at close
+    let reward_amount = market.payout_for(user_winning_amount);
     
     // Transfer tokens (vault -> user)
     let vault_authority_bump = ctx.bumps.vault_authority;
@@ -111,10 +130,20 @@ pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
     if let Some(index) = user_bin_index {
         user_position.bins[index].amount = 0;
     }
+    if is_refund {
+        user_position.cost_basis = 0;
+    }
+    
+    // Drop the claimed bin and shrink the position (rent refunded to the user)
+    position::fit(
+        &user_position.to_account_info(),
+        user_position,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
     
     // Update market state
-    market.collateral_balance = market.collateral_balance.checked_sub(reward_amount)
-        .ok_or(error!(RangeBetError::MathUnderflow))?;
+    market.record_payout(user_winning_amount, reward_amount)?;
     
     // Emit event
     emit!(RewardClaimed {
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, UserMarketPosition, PositionClosed};
+use crate::errors::RangeBetError;
+use crate::position;
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Market of the position (empty once the market has been finalized)
+    #[account(
+        seeds = [b"market", &user_position.market_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: Deserialized as a Market by check_closable unless empty
+    pub market: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pos", user.key().as_ref(), &user_position.market_id.to_le_bytes()],
+        bump,
+        constraint = user_position.owner == user.key() @ RangeBetError::OwnerOnly
+    )]
+    pub user_position: Account<'info, UserMarketPosition>,
+}
+
+/// Batch close: remaining accounts are (market, user position) pairs
+#[derive(Accounts)]
+pub struct ClosePositions<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+/// Require the position's market (`market_ai`, already matched to its PDA) to be resolved with nothing left to claim
+/// An empty market PDA was torn down by finalize_market, which only finalizes settled markets
+fn check_closable(
+    market_ai: &AccountInfo,
+    user_position: &UserMarketPosition,
+    program_id: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    if market_ai.data_is_empty() {
+        return Ok(());
+    }
+
+    require_keys_eq!(*market_ai.owner, *program_id, RangeBetError::InvalidMarketAccount);
+    let market = Market::try_deserialize(&mut &market_ai.try_borrow_data()?[..])?;
+
+    require!(market.closed, RangeBetError::MarketIsNotClosed);
+    require!(!market.has_claim(user_position, now), RangeBetError::PositionHasClaim);
+
+    Ok(())
+}
+
+pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+    check_closable(
+        &ctx.accounts.market.to_account_info(),
+        &ctx.accounts.user_position,
+        ctx.program_id,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let lamports = position::close(
+        &ctx.accounts.user_position.to_account_info(),
+        &ctx.accounts.user.to_account_info(),
+    )?;
+
+    // Emit event
+    emit!(PositionClosed {
+        market_id: ctx.accounts.user_position.market_id,
+        owner: ctx.accounts.user.key(),
+        lamports,
+    });
+
+    msg!("Position closed: Market ID = {}, Owner = {}, Rent = {}",
+        ctx.accounts.user_position.market_id,
+        ctx.accounts.user.key(),
+        lamports
+    );
+
+    Ok(())
+}
+
+/// Close every settled position passed as (market, user position) pairs in the remaining accounts
+/// Finalized markets are passed as their (empty) market PDA
+pub fn close_positions<'info>(ctx: Context<'_, '_, '_, 'info, ClosePositions<'info>>) -> Result<()> {
+    let accounts = ctx.remaining_accounts;
+    require!(!accounts.is_empty(), RangeBetError::NoPositionsToClose);
+    require!(accounts.len() & 1 == 0, RangeBetError::ArrayLengthMismatch);
+
+    let now = Clock::get()?.unix_timestamp;
+    let user = ctx.accounts.user.key();
+    let user_ai = ctx.accounts.user.to_account_info();
+
+    for pair in accounts.chunks(2) {
+        let (market_ai, position_ai) = (&pair[0], &pair[1]);
+
+        // Position must be program-owned and of the expected type (discriminator checked)
+        require!(position_ai.owner == ctx.program_id, RangeBetError::InvalidMarketAccount);
+        let user_position = UserMarketPosition::try_deserialize(&mut &position_ai.try_borrow_data()?[..])?;
+        require_keys_eq!(user_position.owner, user, RangeBetError::OwnerOnly);
+
+        // Market must be the one the position belongs to
+        let (market_key, _) = Pubkey::find_program_address(
+            &[b"market", &user_position.market_id.to_le_bytes()],
+            ctx.program_id,
+        );
+        require_keys_eq!(market_ai.key(), market_key, RangeBetError::InvalidMarketAccount);
+
+        check_closable(market_ai, &user_position, ctx.program_id, now)?;
+
+        let lamports = position::close(position_ai, &user_ai)?;
+
+        emit!(PositionClosed {
+            market_id: user_position.market_id,
+            owner: user,
+            lamports,
+        });
+    }
+
+    msg!("Positions closed: Owner = {}, Count = {}", user, accounts.len() / 2);
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount, Token, Transfer};
+use crate::state::{ProgramState, Market, FeesCollected, Role};
+use crate::errors::RangeBetError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct CollectFees<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = program_state.has_role(Role::Treasurer, &authority.key()) @ RangeBetError::TreasurerOnly
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [b"market", &market_id.to_le_bytes()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint,
+        constraint = treasury_token_account.owner == program_state.treasury @ RangeBetError::TreasurerOnly
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    
+    /// Receives the creator's share of the fees (required when the market has one)
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint,
+        constraint = creator_token_account.owner == market.creator @ RangeBetError::InvalidCreator
+    )]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = market.vault @ RangeBetError::InvalidVault,
+        constraint = vault.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint,
+        constraint = vault.owner == vault_authority.key() @ RangeBetError::InvalidVault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    
+    /// Vault authority PDA (program-signing PDA)
+    #[account(
+        seeds = [b"vault", &market_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: Not an actual account, used as PDA
+    pub vault_authority: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn collect_fees(ctx: Context<CollectFees>, market_id: u64) -> Result<()> {
+    let amount = ctx.accounts.market.fees_accrued;
+    require!(amount > 0, RangeBetError::NoFeesToCollect);
+    
+    // Bonded markets credit their creator a share of the fees
+    let creator_amount = ctx.accounts.market.creator_fee_for(amount);
+    let treasury_amount = amount - creator_amount;
+    
+    // Transfer fees (vault -> treasury, vault -> creator)
+    let vault_authority_bump = ctx.bumps.vault_authority;
+    let market_id_bytes = market_id.to_le_bytes();
+    
+    let seeds = &[
+        b"vault" as &[u8], 
+        &market_id_bytes as &[u8], 
+        &[vault_authority_bump]
+    ];
+    
+    // Bind signer seeds to ensure stability
+    let signer_seeds = &[&seeds[..]];
+    
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault.to_account_info(),
+        to: ctx.accounts.treasury_token_account.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    
+    token::transfer(cpi_ctx, treasury_amount)?;
+    
+    if creator_amount > 0 {
+        let creator_token_account = ctx.accounts.creator_token_account.as_ref()
+            .ok_or(error!(RangeBetError::MissingCreatorTokenAccount))?;
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: creator_token_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            creator_amount,
+        )?;
+    }
+    
+    ctx.accounts.market.fees_accrued = 0;
+    
+    // Emit event
+    emit!(FeesCollected {
+        market_id,
+        to: ctx.accounts.treasury_token_account.key(),
+        amount: treasury_amount,
+        creator_amount,
+    });
+    
+    msg!("Fees collected: Market ID = {}, Treasury = {}, Creator = {}", market_id, treasury_amount, creator_amount);
+    
+    Ok(())
+}
@@ -1,47 +1,81 @@
-use anchor_lang::{prelude::*, system_program};
+use anchor_lang::prelude::*;
 use anchor_spl::{
-    token::{Mint, TokenAccount, Token},
+    token::{self, Mint, TokenAccount, Token, Transfer},
     associated_token::AssociatedToken,
 };
-use crate::state::{ProgramState, Market, MarketCreated};
+use crate::state::{ProgramState, Market, MarketBins, MarketCreated, NoWinnerPolicy, OracleConfig, Role};
+use super::allocate_market_bins::allocate_bins;
 use crate::errors::RangeBetError;
+use range_bet_math_core::ticks;
+
+/// Market configuration passed to create_market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CreateMarketParams {
+    pub tick_spacing: u32,
+    pub min_tick: i64,
+    pub max_tick: i64,
+    pub close_ts: i64,                  // Scheduled close time (must be in the future)
+    pub no_winner_policy: NoWinnerPolicy,
+    pub trading_cutoff: i64,            // Seconds before close_ts at which trading stops
+    pub series_previous: Option<u64>,   // Market that must resolve first (None = independent)
+    pub oracle: Option<OracleConfig>,   // Price feed for resolve_from_oracle (None = manual resolution only)
+    pub dispute_window: i64,            // Seconds a proposed resolution can be overridden
+    pub tail_bins: bool,                // Add underflow/overflow bins
+    pub claim_window: i64,              // Seconds after resolution to claim (0 = no deadline)
+}
 
 #[derive(Accounts)]
-#[instruction(tick_spacing: u32, min_tick: i64, max_tick: i64, close_ts: i64)]
 pub struct CreateMarket<'info> {
     #[account(mut)]
-    pub owner: Signer<'info>,
+    pub authority: Signer<'info>,
     
     #[account(
         mut,
         seeds = [b"range-bet-state"],
-        bump,
-        constraint = program_state.owner == owner.key() @ RangeBetError::OwnerOnly
+        bump
     )]
     pub program_state: Account<'info, ProgramState>,
     
     /// Account to store market information to be created
     #[account(
         init,
-        payer = owner,
+        payer = authority,
         seeds = [b"market", program_state.market_count.to_le_bytes().as_ref()],
         bump,
-        space = 8 + std::mem::size_of::<Market>() // Minimum size (no bins)
+        space = 8 + std::mem::size_of::<Market>()
     )]
     pub market: Account<'info, Market>,
     
+    /// Bin storage for the market (grown by allocate_market_bins beyond the first allocation)
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"bins", program_state.market_count.to_le_bytes().as_ref()],
+        bump,
+        space = MarketBins::space(0)
+    )]
+    pub market_bins: AccountLoader<'info, MarketBins>,
+    
     /// Collateral token Mint
     pub collateral_mint: Account<'info, Mint>,
     
     /// Market's Vault account (stores collateral)
     #[account(
         init,
-        payer = owner,
+        payer = authority,
         associated_token::mint = collateral_mint,
         associated_token::authority = vault_authority
     )]
     pub vault: Account<'info, TokenAccount>,
     
+    /// Creator's collateral account funding the bond (required without the market creator role)
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == collateral_mint.key() @ RangeBetError::InvalidCollateralMint,
+        constraint = creator_token_account.owner == authority.key() @ RangeBetError::InvalidCreator
+    )]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+    
     /// Vault authority PDA (program-signing PDA)
     #[account(
         seeds = [b"vault", program_state.market_count.to_le_bytes().as_ref()],
@@ -56,52 +90,107 @@ pub struct CreateMarket<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn create_market(
-    ctx: Context<CreateMarket>,
-    tick_spacing: u32,
-    min_tick: i64,
-    max_tick: i64,
-    close_ts: i64,
-) -> Result<()> {
+pub fn create_market(ctx: Context<CreateMarket>, params: CreateMarketParams) -> Result<()> {
+    let CreateMarketParams {
+        tick_spacing,
+        min_tick,
+        max_tick,
+        close_ts,
+        no_winner_policy,
+        trading_cutoff,
+        series_previous,
+        oracle,
+        dispute_window,
+        tail_bins,
+        claim_window,
+    } = params;
+    
     // 1. Parameter validation
     require!(tick_spacing > 0, RangeBetError::InvalidTickSpacing);
     require!(min_tick % tick_spacing as i64 == 0, RangeBetError::MinTickNotMultiple);
     require!(max_tick % tick_spacing as i64 == 0, RangeBetError::MaxTickNotMultiple);
     require!(min_tick < max_tick, RangeBetError::MinTickGreaterThanMax);
+    
+    let now = Clock::get()?.unix_timestamp;
+    require!(close_ts > now, RangeBetError::InvalidCloseTime);
+    require!(
+        trading_cutoff >= 0 && close_ts - trading_cutoff > now,
+        RangeBetError::InvalidTradingCutoff
+    );
+
+    // Sequential series must follow an existing market
+    if let Some(previous_id) = series_previous {
+        require!(
+            previous_id < ctx.accounts.program_state.market_count,
+            RangeBetError::InvalidSeriesMarket
+        );
+    }
+
+    // Oracle resolution needs a usable confidence bound and publish window
+    if let Some(config) = oracle {
+        require!(
+            config.max_conf_bps > 0 && config.max_publish_delay >= 0,
+            RangeBetError::InvalidOracleConfig
+        );
+    }
+
+    require!(dispute_window >= 0, RangeBetError::InvalidDisputeWindow);
+    require!(claim_window >= 0, RangeBetError::InvalidClaimWindow);
 
     // 2. Calculate bins length
-    let bin_count = ((max_tick - min_tick) / tick_spacing as i64 + 1) as usize;
+    // Tail bins follow the in-range bins
+    let range_bin_count = ticks::bin_count(min_tick, max_tick, tick_spacing)?;
+    let bin_count = if tail_bins { range_bin_count + 2 } else { range_bin_count };
     
-    // 3. Increase account size and calculate rent
-    let additional_space = 16 * bin_count;  // Vec metadata + u64 data
-    let new_size = 8 + std::mem::size_of::<Market>() + additional_space;
+    // Every bin must be addressable by a u16 index
+    require!(bin_count <= u16::MAX as usize + 1, RangeBetError::TooManyBins);
     
-    // Calculate needed lamports
-    let rent = Rent::get()?;
-    let needed_lamports = rent.minimum_balance(new_size);
-    let market_ai = ctx.accounts.market.to_account_info();
+    let market_id = ctx.accounts.program_state.market_count;
     
-    // If current account's lamports are insufficient, transfer additional lamports
-    if needed_lamports > market_ai.lamports() {
-        let diff = needed_lamports - market_ai.lamports();
+    // Anyone may create a market by posting the creation bond; the market creator role posts none
+    let program_state = &ctx.accounts.program_state;
+    let is_role_creator = program_state.has_role(Role::MarketCreator, &ctx.accounts.authority.key());
+    let bond = if is_role_creator { 0 } else { program_state.creation_bond };
+    require!(is_role_creator || bond > 0, RangeBetError::MarketCreatorOnly);
+    let creator_fee_bps = if is_role_creator { 0 } else { program_state.creator_fee_bps };
+
+    // Bonded creators cannot pick the price feed or shorten the dispute and claim windows
+    if !is_role_creator {
+        require!(oracle.is_none(), RangeBetError::OracleCreatorOnly);
+        require!(
+            dispute_window >= program_state.min_dispute_window,
+            RangeBetError::DisputeWindowTooShort
+        );
+        require!(
+            claim_window == 0 || claim_window >= program_state.min_claim_window,
+            RangeBetError::ClaimWindowTooShort
+        );
+    }
+
+    if bond > 0 {
+        let creator_token_account = ctx.accounts.creator_token_account.as_ref()
+            .ok_or(error!(RangeBetError::MissingCreatorTokenAccount))?;
+        
+        // Transfer bond (creator -> vault), held apart from collateral_balance
+        let cpi_accounts = Transfer {
+            from: creator_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
         
-        // Transfer lamports from owner to market
-        system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.owner.to_account_info(),
-                    to: market_ai.clone(),
-                },
-            ),
-            diff,
-        )?;
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, bond)?;
     }
     
-    // Perform realloc
-    market_ai.realloc(new_size, false)?;
-
-    let market_id = ctx.accounts.program_state.market_count;
+    // 3. Allocate the first bins; larger markets continue with allocate_market_bins
+    ctx.accounts.market_bins.load_init()?.market_id = market_id;
+    let allocated = allocate_bins(
+        &ctx.accounts.market_bins,
+        market_id,
+        bin_count as u32,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+    )?;
     
     // 4. Initialize market
     let market = &mut ctx.accounts.market;
@@ -110,14 +199,37 @@ pub fn create_market(
     market.tick_spacing = tick_spacing;
     market.min_tick = min_tick;
     market.max_tick = max_tick;
+    market.tail_bins = tail_bins;
+    market.bin_count = bin_count as u32;
     market.t_total = 0;
     market.collateral_balance = 0;
+    market.collateral_mint = ctx.accounts.collateral_mint.key();
+    market.vault = ctx.accounts.vault.key();
+    market.creator = ctx.accounts.authority.key();
     market.winning_bin = None; // Not determined yet
-    market.open_ts = Clock::get()?.unix_timestamp;
+    market.open_ts = now;
     market.close_ts = close_ts;
-    
-    // Create and initialize bins array
-    market.bins = vec![0; bin_count];
+    market.trading_cutoff = trading_cutoff;
+    market.series_previous = series_previous;
+    market.no_winner_policy = no_winner_policy;
+    market.oracle = oracle;
+    market.dispute_window = dispute_window;
+    market.proposed_bin = None;
+    market.challenge_deadline = 0;
+    market.total_cost_basis = 0;
+    market.fee_bps = ctx.accounts.program_state.default_fee_bps;
+    market.fees_accrued = 0;
+    market.creator_fee_bps = creator_fee_bps;
+    market.bond = bond;
+    market.payout_pool = 0;
+    market.winning_supply = 0;
+    market.remaining_supply = 0;
+    market.remaining_payout = 0;
+    market.no_winner = false;
+    market.claim_window = claim_window;
+    market.claim_deadline = 0;
+    market.voided = false;
+    market.refund_deadline = 0;
     
     // 5. Update program state
     ctx.accounts.program_state.market_count += 1;
@@ -128,9 +240,11 @@ pub fn create_market(
         tick_spacing,
         min_tick,
         max_tick,
+        creator: ctx.accounts.authority.key(),
+        bond,
     });
     
-    msg!("Market created: ID = {}", market_id);
+    msg!("Market created: ID = {}, Bins allocated = {}/{}, Bond = {}", market_id, allocated, bin_count, bond);
     
     Ok(())
 } 
\ No newline at end of file
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, Market, CloseTimeExtended, Role};
+use crate::errors::RangeBetError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ExtendCloseTs<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = program_state.has_role(Role::MarketCreator, &authority.key()) @ RangeBetError::MarketCreatorOnly
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [b"market", &market_id.to_le_bytes()],
+        bump,
+        constraint = !market.closed @ RangeBetError::MarketClosed,
+        constraint = market.proposed_bin.is_none() @ RangeBetError::ResolutionAlreadyProposed
+    )]
+    pub market: Account<'info, Market>,
+}
+
+pub fn extend_close_ts(ctx: Context<ExtendCloseTs>, market_id: u64, new_close_ts: i64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let old_close_ts = market.close_ts;
+    
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Only while trading is still open, so a known outcome can never reopen trading
+    require!(market.is_trading_open(now), RangeBetError::TradingClosed);
+    
+    // Close time can only move later, and trading must still end in the future
+    require!(new_close_ts > old_close_ts, RangeBetError::CloseTimeNotExtended);
+    require!(
+        new_close_ts.saturating_sub(market.trading_cutoff) > now,
+        RangeBetError::InvalidCloseTime
+    );
+    
+    market.close_ts = new_close_ts;
+    
+    // Emit event
+    emit!(CloseTimeExtended {
+        market_id,
+        old_close_ts,
+        new_close_ts,
+    });
+    
+    msg!("Close time extended: ID = {}, {} -> {}", market_id, old_close_ts, new_close_ts);
+    
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, Market};
+use crate::errors::RangeBetError;
+
+#[derive(Accounts)]
+pub struct SetDefaultFee<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = program_state.owner == owner.key() @ RangeBetError::OwnerOnly
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+#[derive(Accounts)]
+pub struct SetCreatorConfig<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = program_state.owner == owner.key() @ RangeBetError::OwnerOnly
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SetMarketFee<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = program_state.owner == owner.key() @ RangeBetError::OwnerOnly
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [b"market", &market_id.to_le_bytes()],
+        bump,
+        constraint = !market.closed @ RangeBetError::MarketClosed
+    )]
+    pub market: Account<'info, Market>,
+}
+
+pub fn set_default_fee(ctx: Context<SetDefaultFee>, default_fee_bps: u16) -> Result<()> {
+    require!(default_fee_bps <= ProgramState::MAX_FEE_BPS, RangeBetError::InvalidFeeBps);
+    
+    ctx.accounts.program_state.default_fee_bps = default_fee_bps;
+    
+    msg!("Default fee set: {} bps", default_fee_bps);
+    
+    Ok(())
+}
+
+pub fn set_creator_config(
+    ctx: Context<SetCreatorConfig>,
+    creation_bond: u64,
+    creator_fee_bps: u16,
+    min_dispute_window: i64,
+    min_claim_window: i64,
+) -> Result<()> {
+    require!(creator_fee_bps <= 10_000, RangeBetError::InvalidCreatorFeeBps);
+    require!(min_dispute_window >= 0, RangeBetError::InvalidDisputeWindow);
+    require!(min_claim_window >= 0, RangeBetError::InvalidClaimWindow);
+    
+    // Applies to markets created afterwards; existing bonds and shares are unchanged
+    let program_state = &mut ctx.accounts.program_state;
+    program_state.creation_bond = creation_bond;
+    program_state.creator_fee_bps = creator_fee_bps;
+    program_state.min_dispute_window = min_dispute_window;
+    program_state.min_claim_window = min_claim_window;
+    
+    msg!(
+        "Creator config set: Bond = {}, Creator fee = {} bps, Min dispute window = {}, Min claim window = {}",
+        creation_bond, creator_fee_bps, min_dispute_window, min_claim_window
+    );
+    
+    Ok(())
+}
+
+pub fn set_market_fee(ctx: Context<SetMarketFee>, market_id: u64, fee_bps: u16) -> Result<()> {
+    require!(fee_bps <= ProgramState::MAX_FEE_BPS, RangeBetError::InvalidFeeBps);
+    
+    ctx.accounts.market.fee_bps = fee_bps;
+    
+    msg!("Market fee set: ID = {}, Fee = {} bps", market_id, fee_bps);
+    
+    Ok(())
+}
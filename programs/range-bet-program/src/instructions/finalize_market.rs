@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount, Token, Transfer, CloseAccount};
+use crate::state::{ProgramState, Market, MarketBins, MarketFinalized, NoWinnerPolicy, Role};
+use crate::errors::RangeBetError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct FinalizeMarket<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The market's own creator may also tear it down
+    #[account(
+        mut,
+        seeds = [b"market", &market_id.to_le_bytes()],
+        bump,
+        constraint = market.creator == authority.key()
+            || program_state.has_role(Role::MarketCreator, &authority.key()) @ RangeBetError::MarketCreatorOnly,
+        constraint = market.closed @ RangeBetError::MarketIsNotClosed,
+        has_one = creator @ RangeBetError::InvalidCreator,
+        close = creator
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Market bin quantities
+    #[account(
+        mut,
+        seeds = [b"bins", &market_id.to_le_bytes()],
+        bump,
+        close = creator
+    )]
+    pub market_bins: AccountLoader<'info, MarketBins>,
+
+    /// Treasury token account receiving the remaining dust
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint,
+        constraint = treasury_token_account.owner == program_state.treasury @ RangeBetError::TreasurerOnly
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = market.vault @ RangeBetError::InvalidVault,
+        constraint = vault.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint,
+        constraint = vault.owner == vault_authority.key() @ RangeBetError::InvalidVault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Vault authority PDA (program-signing PDA)
+    #[account(
+        seeds = [b"vault", &market_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: Not an actual account, used as PDA
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Receives the rent of the market, its bins and its vault
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn finalize_market(
+    ctx: Context<FinalizeMarket>,
+    market_id: u64,
+) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let now = Clock::get()?.unix_timestamp;
+
+    // Every winner has claimed, or the claim (or refund) period has ended
+    require!(
+        market.remaining_supply == 0 || market.claims_expired(now),
+        RangeBetError::MarketNotSettled
+    );
+    
+    // Unclaimed payouts cannot be swept while users are unable to claim them
+    require!(
+        market.remaining_supply == 0 || !ctx.accounts.program_state.claims_paused(),
+        RangeBetError::ClaimsPaused
+    );
+
+    // The bond goes back to the creator or the treasury through settle_bond, not the sweep
+    require!(market.bond == 0, RangeBetError::BondNotSettled);
+    
+    // The creator's share of the fees is paid out by collect_fees
+    require!(
+        market.fees_accrued == 0 || market.creator_fee_bps == 0,
+        RangeBetError::FeesNotCollected
+    );
+    
+    // A rolled-over pool belongs to the next market, not the treasury
+    if market.has_no_winner() && market.no_winner_policy == NoWinnerPolicy::RollOver {
+        require!(market.collateral_balance == 0, RangeBetError::RollOverPending);
+    }
+
+    let vault_authority_bump = ctx.bumps.vault_authority;
+    let market_id_bytes = market_id.to_le_bytes();
+
+    let seeds = &[
+        b"vault" as &[u8],
+        &market_id_bytes as &[u8],
+        &[vault_authority_bump]
+    ];
+
+    // Bind signer seeds to ensure stability
+    let signer_seeds = &[&seeds[..]];
+
+    // Sweep whatever is left in the vault (rounding dust, unclaimed refunds, uncollected protocol fees) to the treasury
+    let swept = ctx.accounts.vault.amount;
+    if swept > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            swept,
+        )?;
+    }
+
+    // Close the empty vault, returning its rent to the creator
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.vault.to_account_info(),
+        destination: ctx.accounts.creator.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    ))?;
+
+    // Market and bins accounts are closed on exit
+
+    // Emit event
+    emit!(MarketFinalized {
+        market_id,
+        creator: ctx.accounts.creator.key(),
+        swept,
+    });
+
+    msg!("Market finalized: ID = {}, Swept = {}", market_id, swept);
+
+    Ok(())
+}
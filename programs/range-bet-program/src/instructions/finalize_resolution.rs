@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, Market, MarketBins, MarketClosed, NoWinner, NoWinnerPolicy};
+use crate::errors::RangeBetError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct FinalizeResolution<'info> {
+    /// Anyone can finalize once the challenge window has passed
+    pub finalizer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"range-bet-state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [b"market", &market_id.to_le_bytes()],
+        bump,
+        constraint = !market.closed @ RangeBetError::MarketClosed
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// Market bin quantities
+    #[account(
+        seeds = [b"bins", &market_id.to_le_bytes()],
+        bump
+    )]
+    pub market_bins: AccountLoader<'info, MarketBins>,
+    
+    /// Previous market in the series (required if the market is part of a sequential series)
+    /// CHECK: Verified against the series PDA; a finalized predecessor has no data left
+    pub series_previous: Option<UncheckedAccount<'info>>,
+}
+
+pub fn finalize_resolution(
+    ctx: Context<FinalizeResolution>, 
+    market_id: u64,
+) -> Result<()> {
+    let winning_bin = ctx.accounts.market.proposed_bin
+        .ok_or(error!(RangeBetError::NoResolutionProposed))?;
+    
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Proposal becomes final only after the challenge window
+    require!(
+        now >= ctx.accounts.market.challenge_deadline,
+        RangeBetError::ChallengeWindowOpen
+    );
+    
+    // Markets in a sequential series resolve only after their predecessor
+    if let Some(previous_id) = ctx.accounts.market.series_previous {
+        let previous = ctx.accounts.series_previous.as_ref()
+            .ok_or(error!(RangeBetError::IncorrectMarketOrderForClosing))?;
+        let (previous_key, _) = Pubkey::find_program_address(
+            &[b"market", &previous_id.to_le_bytes()],
+            ctx.program_id,
+        );
+        require!(previous.key() == previous_key, RangeBetError::IncorrectMarketOrderForClosing);
+        
+        // finalize_market only tears down resolved markets
+        let previous_closed = previous.data_is_empty()
+            || Market::try_deserialize(&mut &previous.try_borrow_data()?[..])?.closed;
+        require!(previous_closed, RangeBetError::IncorrectMarketOrderForClosing);
+    }
+    
+    // A partly allocated market never traded, so its winning bin may have no storage yet
+    let winning_bin_q = MarketBins::get_or_zero(
+        &ctx.accounts.market_bins.to_account_info().try_borrow_data()?,
+        winning_bin,
+    );
+    
+    // Get mutable references
+    let market = &mut ctx.accounts.market;
+    let program_state = &mut ctx.accounts.program_state;
+    
+    // Update market state
+    market.closed = true;
+    market.winning_bin = Some(winning_bin);
+    market.proposed_bin = None;
+    
+    // Snapshot the prize pool so claim order doesn't change payouts
+    market.payout_pool = market.collateral_balance;
+    market.winning_supply = winning_bin_q;
+    market.remaining_supply = market.winning_supply;
+    market.remaining_payout = market.payout_pool;
+    
+    // Unclaimed payouts can be swept once the claim window has passed
+    if market.claim_window > 0 {
+        market.claim_deadline = now.checked_add(market.claim_window)
+            .ok_or(error!(RangeBetError::MathOverflow))?;
+    }
+    
+    // Winning bin is empty: apply the market's no-winner policy
+    market.no_winner = winning_bin_q == 0;
+    if market.no_winner {
+        // Nothing to refund against, so the pool goes to the treasury
+        if market.no_winner_policy == NoWinnerPolicy::Refund && market.total_cost_basis == 0 {
+            market.no_winner_policy = NoWinnerPolicy::Treasury;
+        }
+        
+        // Refunds are claimed pro rata to cost basis
+        if market.no_winner_policy == NoWinnerPolicy::Refund {
+            market.winning_supply = market.total_cost_basis;
+            market.remaining_supply = market.total_cost_basis;
+        }
+        
+        emit!(NoWinner {
+            market_id,
+            policy: market.no_winner_policy,
+            pool: market.payout_pool,
+        });
+        
+        msg!("No winner: ID = {}, Policy = {:?}", market_id, market.no_winner_policy);
+    }
+    
+    // Update program state - closed market counter
+    program_state.closed_market_count += 1;
+    
+    // Emit event
+    emit!(MarketClosed {
+        market_id,
+        winning_bin,
+    });
+    
+    msg!("Market closed: ID = {}, Winning bin index = {}", market_id, winning_bin);
+    
+    Ok(())
+} 
\ No newline at end of file
@@ -25,8 +25,25 @@ pub fn initialize_program(ctx: Context<InitializeProgram>) -> Result<()> {
     program_state.owner = ctx.accounts.initializer.key();
     // Initialize market count
     program_state.market_count = 0;
-    // Initialize last closed market (None = no closed markets yet)
-    program_state.last_closed_market = None;
+    // Initialize closed market counter
+    program_state.closed_market_count = 0;
+    // Owner holds every role until it is granted to another account
+    program_state.market_creator = program_state.owner;
+    program_state.resolver = program_state.owner;
+    program_state.pauser = program_state.owner;
+    program_state.treasury = program_state.owner;
+    // No protocol fee by default
+    program_state.default_fee_bps = 0;
+    // No ownership transfer pending
+    program_state.pending_owner = Pubkey::default();
+    // Not paused
+    program_state.paused = false;
+    program_state.claims_open_while_paused = false;
+    // Only the market creator role can create markets until a bond is configured
+    program_state.creation_bond = 0;
+    program_state.creator_fee_bps = 0;
+    program_state.min_dispute_window = 0;
+    program_state.min_claim_window = 0;
     
     msg!("Program initialized: owner = {}", program_state.owner);
     
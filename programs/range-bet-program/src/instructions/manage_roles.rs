@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, Role, RoleGranted, RoleRevoked};
+use crate::errors::RangeBetError;
+
+#[derive(Accounts)]
+pub struct ManageRoles<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = program_state.owner == owner.key() @ RangeBetError::OwnerOnly
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+pub fn grant_role(ctx: Context<ManageRoles>, role: Role, account: Pubkey) -> Result<()> {
+    ctx.accounts.program_state.set_role_holder(role, account);
+    
+    // Emit event
+    emit!(RoleGranted {
+        role,
+        account,
+    });
+    
+    msg!("Role granted: {:?} = {}", role, account);
+    
+    Ok(())
+}
+
+pub fn revoke_role(ctx: Context<ManageRoles>, role: Role) -> Result<()> {
+    let program_state = &mut ctx.accounts.program_state;
+    let account = program_state.role_holder(role);
+    
+    // Role falls back to the owner
+    let owner = program_state.owner;
+    program_state.set_role_holder(role, owner);
+    
+    // Emit event
+    emit!(RoleRevoked {
+        role,
+        account,
+    });
+    
+    msg!("Role revoked: {:?} from {}", role, account);
+    
+    Ok(())
+}
@@ -1,19 +1,53 @@
 // Export all instruction modules
 pub mod initialize_program;
 pub mod create_market;
+pub mod allocate_market_bins;
 pub mod buy_tokens;
-pub mod close_market;
+pub mod buy_with_budget;
+pub mod sell_tokens;
+pub mod propose_resolution;
+pub mod override_resolution;
+pub mod finalize_resolution;
 pub mod claim_reward;
 pub mod toggle_market_status;
 pub mod withdraw_collateral;
 pub mod transfer_position;
+pub mod void_market;
+pub mod refund_position;
+pub mod extend_close_ts;
+pub mod resolve_from_oracle;
+pub mod close_position;
+pub mod finalize_market;
+pub mod fee_config;
+pub mod collect_fees;
+pub mod manage_roles;
+pub mod transfer_ownership;
+pub mod set_paused;
+pub mod settle_bond;
 
 // Export common context structures used in instructions
 pub use initialize_program::*;
 pub use create_market::*;
+pub use allocate_market_bins::*;
 pub use buy_tokens::*;
-pub use close_market::*;
+pub use buy_with_budget::*;
+pub use sell_tokens::*;
+pub use propose_resolution::*;
+pub use override_resolution::*;
+pub use finalize_resolution::*;
 pub use claim_reward::*;
 pub use toggle_market_status::*;
 pub use withdraw_collateral::*;
-pub use transfer_position::*;
\ No newline at end of file
+pub use transfer_position::*;
+pub use void_market::*;
+pub use refund_position::*;
+pub use extend_close_ts::*;
+pub use resolve_from_oracle::*;
+pub use close_position::*;
+pub use finalize_market::*;
+pub use fee_config::*;
+pub use collect_fees::*;
+pub use manage_roles::*;
+pub use transfer_ownership::*;
+pub use set_paused::*;
+pub use settle_bond::*;
\ No newline at end of file
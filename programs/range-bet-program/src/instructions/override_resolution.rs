@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, Market, Role, ResolutionOverridden};
+use crate::errors::RangeBetError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct OverrideResolution<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = program_state.has_role(Role::Resolver, &authority.key()) @ RangeBetError::ResolverOnly
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [b"market", &market_id.to_le_bytes()],
+        bump,
+        constraint = !market.closed @ RangeBetError::MarketClosed
+    )]
+    pub market: Account<'info, Market>,
+}
+
+pub fn override_resolution(
+    ctx: Context<OverrideResolution>,
+    market_id: u64,
+    winning_bin: u16,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    let old_winning_bin = market.proposed_bin
+        .ok_or(error!(RangeBetError::NoResolutionProposed))?;
+    
+    // Proposals can only be corrected while the challenge window is open
+    require!(
+        Clock::get()?.unix_timestamp < market.challenge_deadline,
+        RangeBetError::ChallengeWindowClosed
+    );
+    
+    require!(
+        (winning_bin as u32) < market.bin_count,
+        RangeBetError::BinIndexOutOfRange
+    );
+    
+    market.proposed_bin = Some(winning_bin);
+    
+    // Emit event
+    emit!(ResolutionOverridden {
+        market_id,
+        authority: ctx.accounts.authority.key(),
+        old_winning_bin,
+        new_winning_bin: winning_bin,
+    });
+    
+    msg!("Resolution overridden: ID = {}, Winning bin index = {} -> {}",
+        market_id,
+        old_winning_bin,
+        winning_bin
+    );
+    
+    Ok(())
+}
+
+/// Override with the bin whose lower tick is `winning_tick` (or a tail bin for ticks outside the market)
+pub fn override_resolution_at_tick(
+    ctx: Context<OverrideResolution>,
+    market_id: u64,
+    winning_tick: i64,
+) -> Result<()> {
+    let winning_bin = ctx.accounts.market.bin_for_tick(winning_tick)?;
+    
+    override_resolution(ctx, market_id, winning_bin)
+}
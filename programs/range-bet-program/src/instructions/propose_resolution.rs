@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, Market, Role, ResolutionProposed};
+use crate::errors::RangeBetError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ProposeResolution<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = program_state.has_role(Role::Resolver, &authority.key()) @ RangeBetError::ResolverOnly
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [b"market", &market_id.to_le_bytes()],
+        bump,
+        constraint = !market.closed @ RangeBetError::MarketClosed
+    )]
+    pub market: Account<'info, Market>,
+}
+
+pub fn propose_resolution(
+    ctx: Context<ProposeResolution>,
+    market_id: u64,
+    winning_bin: u16,
+) -> Result<()> {
+    propose_winning_bin(
+        &mut ctx.accounts.market,
+        market_id,
+        winning_bin,
+        ctx.accounts.authority.key(),
+    )
+}
+
+/// Propose the bin whose lower tick is `winning_tick` (or a tail bin for ticks outside the market)
+pub fn propose_resolution_at_tick(
+    ctx: Context<ProposeResolution>,
+    market_id: u64,
+    winning_tick: i64,
+) -> Result<()> {
+    let winning_bin = ctx.accounts.market.bin_for_tick(winning_tick)?;
+    
+    propose_resolution(ctx, market_id, winning_bin)
+}
+
+/// Record `winning_bin` as pending and open the challenge window
+pub(crate) fn propose_winning_bin(
+    market: &mut Market,
+    market_id: u64,
+    winning_bin: u16,
+    proposer: Pubkey,
+) -> Result<()> {
+    require!(market.proposed_bin.is_none(), RangeBetError::ResolutionAlreadyProposed);
+    
+    // Resolution is only allowed once the market has reached its close time
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= market.close_ts, RangeBetError::CloseTimeNotReached);
+    
+    // Check if the winning bin index is within the bins array range
+    require!(
+        (winning_bin as u32) < market.bin_count,
+        RangeBetError::BinIndexOutOfRange
+    );
+    
+    let challenge_deadline = now.saturating_add(market.dispute_window);
+    market.proposed_bin = Some(winning_bin);
+    market.challenge_deadline = challenge_deadline;
+    
+    // Emit event
+    emit!(ResolutionProposed {
+        market_id,
+        proposer,
+        winning_bin,
+        challenge_deadline,
+    });
+    
+    msg!("Resolution proposed: ID = {}, Winning bin index = {}, Challenge deadline = {}",
+        market_id,
+        winning_bin,
+        challenge_deadline
+    );
+    
+    Ok(())
+}
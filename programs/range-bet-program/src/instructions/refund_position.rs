@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount, Token, Transfer};
+use crate::state::{ProgramState, Market, UserMarketPosition, PositionRefunded};
+use crate::errors::RangeBetError;
+use crate::position;
+
+#[derive(Accounts)]
+pub struct RefundPosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Program state (global pause)
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = !program_state.claims_paused() @ RangeBetError::ClaimsPaused
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [b"market", &user_position.market_id.to_le_bytes()],
+        bump,
+        constraint = market.voided @ RangeBetError::MarketNotVoided
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"pos", user.key().as_ref(), &user_position.market_id.to_le_bytes()],
+        bump,
+        constraint = user_position.owner == user.key() @ RangeBetError::OwnerOnly
+    )]
+    pub user_position: Account<'info, UserMarketPosition>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = market.vault @ RangeBetError::InvalidVault,
+        constraint = vault.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint,
+        constraint = vault.owner == vault_authority.key() @ RangeBetError::InvalidVault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Vault authority PDA (program signing PDA)
+    #[account(
+        seeds = [b"vault", &user_position.market_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: This is not an actual account but used as a PDA
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn refund_position(ctx: Context<RefundPosition>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let user_position = &mut ctx.accounts.user_position;
+
+    // Refunds are only available until the deadline
+    require!(
+        Clock::get()?.unix_timestamp <= market.refund_deadline,
+        RangeBetError::RefundPeriodEnded
+    );
+
+    let cost_basis = user_position.cost_basis;
+    require!(cost_basis > 0, RangeBetError::NoRefundAvailable);
+
+    // Refund pro rata to cost basis from the snapshot taken at void
+    let refund_amount = market.payout_for(cost_basis);
+
+    // Transfer tokens (vault -> user)
+    let vault_authority_bump = ctx.bumps.vault_authority;
+    let seeds = &[
+        b"vault" as &[u8],
+        &user_position.market_id.to_le_bytes()[..] as &[u8],
+        &[vault_authority_bump]
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    token::transfer(cpi_ctx, refund_amount)?;
+
+    // Clear the position
+    user_position.cost_basis = 0;
+    for bin_bal in &mut user_position.bins {
+        bin_bal.amount = 0;
+    }
+
+    // Drop the cleared bins and shrink the position (rent refunded to the user)
+    position::fit(
+        &user_position.to_account_info(),
+        user_position,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Update market state
+    market.record_payout(cost_basis, refund_amount)?;
+
+    // Emit event
+    emit!(PositionRefunded {
+        market_id: user_position.market_id,
+        owner: ctx.accounts.user.key(),
+        amount: refund_amount,
+    });
+
+    msg!("Position refunded: Market ID = {}, Owner = {}, Amount = {}",
+        user_position.market_id,
+        ctx.accounts.user.key(),
+        refund_amount
+    );
+
+    Ok(())
+}
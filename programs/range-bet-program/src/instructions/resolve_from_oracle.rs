@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, OracleResolved};
+use crate::errors::RangeBetError;
+use crate::oracle;
+use super::propose_resolution::propose_winning_bin;
+use range_bet_math_core::ticks;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct ResolveFromOracle<'info> {
+    /// Anyone can propose a resolution from the market's configured price feed
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"market", &market_id.to_le_bytes()],
+        bump,
+        constraint = !market.closed @ RangeBetError::MarketClosed
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// Price feed account configured for the market
+    /// CHECK: Address and owner are checked against the market's oracle config and data is parsed by format
+    pub price_feed: UncheckedAccount<'info>,
+}
+
+pub fn resolve_from_oracle(
+    ctx: Context<ResolveFromOracle>,
+    market_id: u64,
+) -> Result<()> {
+    let config = ctx.accounts.market.oracle
+        .ok_or(error!(RangeBetError::OracleNotConfigured))?;
+    require_keys_eq!(
+        ctx.accounts.price_feed.key(),
+        config.feed,
+        RangeBetError::InvalidOracleAccount
+    );
+    require_keys_eq!(
+        *ctx.accounts.price_feed.owner,
+        config.feed_owner,
+        RangeBetError::InvalidOracleAccount
+    );
+    
+    let close_ts = ctx.accounts.market.close_ts;
+    require!(
+        Clock::get()?.unix_timestamp >= close_ts,
+        RangeBetError::CloseTimeNotReached
+    );
+    
+    let price = oracle::read_price(config.format, &ctx.accounts.price_feed.try_borrow_data()?)?;
+    
+    // Price must be published at or shortly after the close time
+    require!(
+        price.publish_time >= close_ts
+            && price.publish_time <= close_ts.saturating_add(config.max_publish_delay),
+        RangeBetError::OraclePriceOutsideWindow
+    );
+    
+    // Confidence interval must be within max_conf_bps of the price
+    require!(price.price > 0, RangeBetError::OraclePriceUnavailable);
+    require!(
+        price.conf as u128 * 10_000 <= price.price as u128 * config.max_conf_bps as u128,
+        RangeBetError::OracleConfidenceTooWide
+    );
+    
+    // Derive the winning bin from the price
+    let tick = ticks::price_to_tick(price.price, price.expo, config.tick_expo);
+    let winning_bin = oracle::tick_to_bin(&ctx.accounts.market, tick)?;
+    
+    emit!(OracleResolved {
+        market_id,
+        price: price.price,
+        expo: price.expo,
+        publish_time: price.publish_time,
+        tick,
+        winning_bin,
+    });
+    
+    msg!("Oracle price: {} (expo {}), Tick = {}", price.price, price.expo, tick);
+    
+    propose_winning_bin(
+        &mut ctx.accounts.market,
+        market_id,
+        winning_bin,
+        ctx.accounts.proposer.key(),
+    )
+}
@@ -0,0 +1,224 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount, Token, Transfer};
+use crate::state::{ProgramState, Market, MarketBins, UserMarketPosition, TokensSold};
+use crate::errors::RangeBetError;
+use crate::position;
+use range_bet_math_core::RangeBetMath;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SellTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Program state (global pause)
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = !program_state.trading_paused() @ RangeBetError::ProgramPaused
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Market account
+    #[account(
+        mut,
+        seeds = [b"market", &market_id.to_le_bytes()],
+        bump,
+        constraint = market.active @ RangeBetError::MarketNotActive,
+        constraint = !market.closed @ RangeBetError::MarketClosed
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Market bin quantities
+    #[account(
+        mut,
+        seeds = [b"bins", &market_id.to_le_bytes()],
+        bump
+    )]
+    pub market_bins: AccountLoader<'info, MarketBins>,
+
+    /// User position
+    #[account(
+        mut,
+        seeds = [b"pos", user.key().as_ref(), &market_id.to_le_bytes()],
+        bump,
+        constraint = user_position.owner == user.key() @ RangeBetError::OwnerOnly
+    )]
+    pub user_position: Account<'info, UserMarketPosition>,
+
+    /// User token account
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// Market Vault account
+    #[account(
+        mut,
+        address = market.vault @ RangeBetError::InvalidVault,
+        constraint = vault.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint,
+        constraint = vault.owner == vault_authority.key() @ RangeBetError::InvalidVault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Vault authority PDA (program-signing PDA)
+    #[account(
+        seeds = [b"vault", &market_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: Not an actual account, used as PDA
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn sell_tokens(
+    ctx: Context<SellTokens>,
+    market_id: u64,
+    bin_indices: Vec<u16>,
+    amounts: Vec<u64>,
+    min_collateral_out: u64,
+) -> Result<()> {
+    // Validation
+    require!(bin_indices.len() == amounts.len(), RangeBetError::ArrayLengthMismatch);
+    require!(!bin_indices.is_empty(), RangeBetError::NoTokensToSell);
+
+    // Trading stops at close_ts minus the trading cutoff
+    require!(
+        ctx.accounts.market.is_trading_open(Clock::get()?.unix_timestamp),
+        RangeBetError::TradingClosed
+    );
+
+    let market = &mut ctx.accounts.market;
+    let user_position = &mut ctx.accounts.user_position;
+    let bins_ai = ctx.accounts.market_bins.to_account_info();
+    let mut bins = bins_ai.try_borrow_mut_data()?;
+    MarketBins::require_allocated(&bins, market.bin_count)?;
+
+    let mut t_current = market.t_total;
+    let mut total_revenue: u64 = 0;
+    let mut sold: Vec<(u16, u64)> = Vec::with_capacity(bin_indices.len());
+
+    // Process each bin
+    for i in 0..bin_indices.len() {
+        let index = bin_indices[i];
+        let amount = amounts[i];
+
+        // Skip if amount is 0
+        if amount == 0 {
+            continue;
+        }
+
+        // Get quantity from market bin (checks the index range)
+        let bin_q = MarketBins::get(&bins, index)?;
+        require!(bin_q > 0, RangeBetError::CannotSellFromEmptyBin);
+        require!(amount <= bin_q, RangeBetError::CannotSellMoreThanBin);
+
+        // Debit user position
+        let bin_bal = user_position.bins
+            .iter_mut()
+            .find(|bin_bal| bin_bal.index == index)
+            .ok_or(error!(RangeBetError::InsufficientTokensToSell))?;
+        require!(bin_bal.amount >= amount, RangeBetError::InsufficientTokensToSell);
+        bin_bal.amount -= amount;
+
+        // Calculate revenue
+        let revenue = RangeBetMath::calculate_bin_sell_cost(amount, bin_q, t_current)?;
+        sold.push((index, revenue));
+        total_revenue = total_revenue.checked_add(revenue).ok_or(error!(RangeBetError::MathOverflow))?;
+
+        // Update market bin quantity
+        MarketBins::set(&mut bins, index, bin_q - amount)?;
+
+        // Update T
+        t_current = t_current.checked_sub(amount).ok_or(error!(RangeBetError::CannotSellMoreThanSupply))?;
+    }
+
+    // Protocol fee is deducted from the revenue and stays in the vault
+    let fee = market.fee_for(total_revenue);
+    let net_revenue = total_revenue - fee;
+
+    // Check if revenue falls below minimum collateral out
+    require!(net_revenue >= min_collateral_out, RangeBetError::RevenueBelowMinCollateral);
+
+    // Each bin records what the user is paid for it: its revenue less its pro rata share of the fee
+    let mut cumulative_revenue: u128 = 0;
+    let mut fee_charged: u64 = 0;
+    for (index, revenue) in sold {
+        cumulative_revenue += revenue as u128;
+        let fee_through = (fee as u128 * cumulative_revenue)
+            .checked_div(total_revenue as u128)
+            .unwrap_or(0) as u64;
+        let net = revenue - (fee_through - fee_charged);
+        fee_charged = fee_through;
+
+        let bin_bal = user_position.bins
+            .iter_mut()
+            .find(|bin_bal| bin_bal.index == index)
+            .ok_or(error!(RangeBetError::InsufficientTokensToSell))?;
+        bin_bal.received = bin_bal.received.checked_add(net).ok_or(error!(RangeBetError::MathOverflow))?;
+    }
+
+    // Update market state
+    market.t_total = t_current;
+    market.collateral_balance = market.collateral_balance.checked_sub(total_revenue)
+        .ok_or(error!(RangeBetError::MathUnderflow))?;
+    market.fees_accrued = market.fees_accrued.checked_add(fee)
+        .ok_or(error!(RangeBetError::MathOverflow))?;
+    
+    // Reduce cost basis (never below zero)
+    let basis_reduction = net_revenue.min(user_position.cost_basis);
+    user_position.cost_basis -= basis_reduction;
+    market.total_cost_basis = market.total_cost_basis.saturating_sub(basis_reduction);
+
+    // Drop sold-out bins and shrink the position (rent refunded to the user)
+    position::fit(
+        &user_position.to_account_info(),
+        user_position,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Transfer tokens (vault -> user)
+    let vault_authority_bump = ctx.bumps.vault_authority;
+    let market_id_bytes = market_id.to_le_bytes();
+    let seeds = &[
+        b"vault" as &[u8],
+        &market_id_bytes as &[u8],
+        &[vault_authority_bump]
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    token::transfer(cpi_ctx, net_revenue)?;
+
+    // Emit event
+    emit!(TokensSold {
+        market_id,
+        seller: ctx.accounts.user.key(),
+        total_revenue,
+        fee,
+    });
+
+    msg!("Token sale complete: Market ID = {}, Seller = {}, Revenue = {}, Fee = {}",
+        market_id,
+        ctx.accounts.user.key(),
+        total_revenue,
+        fee
+    );
+
+    Ok(())
+}
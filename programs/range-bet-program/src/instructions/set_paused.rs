@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, Role, ProgramPauseSet};
+use crate::errors::RangeBetError;
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = program_state.has_role(Role::Pauser, &authority.key()) @ RangeBetError::PauserOnly
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+pub fn set_paused(ctx: Context<SetPaused>, paused: bool, claims_open: bool) -> Result<()> {
+    let program_state = &mut ctx.accounts.program_state;
+    
+    // Pause (or resume) every market at once; `claims_open` keeps claims and refunds available
+    program_state.paused = paused;
+    program_state.claims_open_while_paused = paused && claims_open;
+    
+    // Emit event
+    emit!(ProgramPauseSet {
+        paused,
+        claims_open: !program_state.claims_paused(),
+    });
+    
+    if paused {
+        msg!("Program paused: claims open = {}", claims_open);
+    } else {
+        msg!("Program unpaused");
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount, Token, Transfer};
+use crate::state::{ProgramState, Market, BondSettled};
+use crate::errors::RangeBetError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct SettleBond<'info> {
+    /// Anyone may settle a resolved market's bond
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        seeds = [b"market", &market_id.to_le_bytes()],
+        bump,
+        constraint = market.closed @ RangeBetError::MarketIsNotClosed
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// Receives the bond after a clean resolution
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint,
+        constraint = creator_token_account.owner == market.creator @ RangeBetError::InvalidCreator
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+    
+    /// Receives the bond slashed from a voided market
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint,
+        constraint = treasury_token_account.owner == program_state.treasury @ RangeBetError::TreasurerOnly
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        address = market.vault @ RangeBetError::InvalidVault,
+        constraint = vault.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint,
+        constraint = vault.owner == vault_authority.key() @ RangeBetError::InvalidVault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    
+    /// Vault authority PDA (program-signing PDA)
+    #[account(
+        seeds = [b"vault", &market_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: Not an actual account, used as PDA
+    pub vault_authority: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn settle_bond(ctx: Context<SettleBond>, market_id: u64) -> Result<()> {
+    let amount = ctx.accounts.market.bond;
+    require!(amount > 0, RangeBetError::NoBondToSettle);
+    
+    // Returned after a clean resolution, slashed to the treasury if the market was voided
+    let slashed = ctx.accounts.market.voided;
+    let to = if slashed {
+        ctx.accounts.treasury_token_account.to_account_info()
+    } else {
+        ctx.accounts.creator_token_account.to_account_info()
+    };
+    
+    // Transfer bond (vault -> creator or treasury)
+    let vault_authority_bump = ctx.bumps.vault_authority;
+    let market_id_bytes = market_id.to_le_bytes();
+    
+    let seeds = &[
+        b"vault" as &[u8], 
+        &market_id_bytes as &[u8], 
+        &[vault_authority_bump]
+    ];
+    
+    // Bind signer seeds to ensure stability
+    let signer_seeds = &[&seeds[..]];
+    
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault.to_account_info(),
+        to,
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    
+    token::transfer(cpi_ctx, amount)?;
+    
+    ctx.accounts.market.bond = 0;
+    
+    // Emit event
+    emit!(BondSettled {
+        market_id,
+        creator: ctx.accounts.market.creator,
+        amount,
+        slashed,
+    });
+    
+    if slashed {
+        msg!("Bond slashed: Market ID = {}, Amount = {}", market_id, amount);
+    } else {
+        msg!("Bond returned: Market ID = {}, Amount = {}", market_id, amount);
+    }
+    
+    Ok(())
+}
@@ -1,17 +1,17 @@
 use anchor_lang::prelude::*;
-use crate::state::{ProgramState, Market};
+use crate::state::{ProgramState, Market, Role};
 use crate::errors::RangeBetError;
 
 #[derive(Accounts)]
 #[instruction(market_id: u64)]
 pub struct ToggleMarketStatus<'info> {
     #[account(mut)]
-    pub owner: Signer<'info>,
+    pub authority: Signer<'info>,
     
     #[account(
         seeds = [b"range-bet-state"],
         bump,
-        constraint = program_state.owner == owner.key() @ RangeBetError::OwnerOnly
+        constraint = program_state.has_role(Role::Pauser, &authority.key()) @ RangeBetError::PauserOnly
     )]
     pub program_state: Account<'info, ProgramState>,
     
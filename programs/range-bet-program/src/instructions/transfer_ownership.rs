@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    ProgramState, Role, OwnershipTransferProposed, OwnershipTransferCancelled, OwnershipTransferred,
+};
+use crate::errors::RangeBetError;
+
+#[derive(Accounts)]
+pub struct ProposeOwner<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = program_state.owner == owner.key() @ RangeBetError::OwnerOnly
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptOwnership<'info> {
+    pub new_owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = program_state.pending_owner != Pubkey::default() @ RangeBetError::NoPendingOwner,
+        constraint = program_state.pending_owner == new_owner.key() @ RangeBetError::PendingOwnerOnly
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+pub fn propose_owner(ctx: Context<ProposeOwner>, new_owner: Pubkey) -> Result<()> {
+    let program_state = &mut ctx.accounts.program_state;
+    
+    // The default key marks "no transfer pending", and proposing the current owner is a no-op
+    require!(
+        new_owner != Pubkey::default() && new_owner != program_state.owner,
+        RangeBetError::InvalidNewOwner
+    );
+    
+    // Replaces any previously proposed owner
+    program_state.pending_owner = new_owner;
+    
+    // Emit event
+    emit!(OwnershipTransferProposed {
+        owner: program_state.owner,
+        pending_owner: new_owner,
+    });
+    
+    msg!("Ownership transfer proposed: {} -> {}", program_state.owner, new_owner);
+    
+    Ok(())
+}
+
+pub fn cancel_ownership_transfer(ctx: Context<ProposeOwner>) -> Result<()> {
+    let program_state = &mut ctx.accounts.program_state;
+    
+    let pending_owner = program_state.pending_owner;
+    require!(pending_owner != Pubkey::default(), RangeBetError::NoPendingOwner);
+    
+    program_state.pending_owner = Pubkey::default();
+    
+    // Emit event
+    emit!(OwnershipTransferCancelled {
+        owner: program_state.owner,
+        pending_owner,
+    });
+    
+    msg!("Ownership transfer cancelled: {}", pending_owner);
+    
+    Ok(())
+}
+
+pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+    let program_state = &mut ctx.accounts.program_state;
+    
+    let previous_owner = program_state.owner;
+    let new_owner = program_state.pending_owner;
+    
+    program_state.owner = new_owner;
+    program_state.pending_owner = Pubkey::default();
+    
+    // Roles still held by the previous owner move with ownership, so a rotated key keeps no powers
+    for role in [Role::MarketCreator, Role::Resolver, Role::Pauser, Role::Treasurer] {
+        if program_state.role_holder(role) == previous_owner {
+            program_state.set_role_holder(role, new_owner);
+        }
+    }
+    
+    // Emit event
+    emit!(OwnershipTransferred {
+        previous_owner,
+        new_owner,
+    });
+    
+    msg!("Ownership transferred: {} -> {}", previous_owner, new_owner);
+    
+    Ok(())
+}
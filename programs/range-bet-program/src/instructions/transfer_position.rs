@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::state::{Market, UserMarketPosition, BinBal};
+use crate::state::{ProgramState, Market, UserMarketPosition, BinBal};
 use crate::errors::RangeBetError;
+use crate::position;
 
 #[derive(Accounts)]
 #[instruction(market_id: u64)]
@@ -8,6 +9,14 @@ pub struct TransferPosition<'info> {
     #[account(mut)]
     pub from_user: Signer<'info>,
     
+    /// Program state (global pause)
+    #[account(
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = !program_state.trading_paused() @ RangeBetError::ProgramPaused
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
     /// Recipient of the position transfer
     /// CHECK: Actual user account or PDA
     pub to_user: UncheckedAccount<'info>,
@@ -28,15 +37,14 @@ pub struct TransferPosition<'info> {
     )]
     pub from_position: Account<'info, UserMarketPosition>,
     
-    /// Recipient position (created if doesn't exist)
+    /// Recipient position (created if doesn't exist, resized to fit its bins)
     #[account(
-        init_if_needed,
-        payer = from_user,
-        space = 8 + std::mem::size_of::<UserMarketPosition>() + 16 * 100, // Reserve space for 100 bins
+        mut,
         seeds = [b"pos", to_user.key().as_ref(), &market_id.to_le_bytes()],
         bump,
     )]
-    pub to_position: Account<'info, UserMarketPosition>,
+    /// CHECK: Created or deserialized as a UserMarketPosition by the position helpers
+    pub to_position: UncheckedAccount<'info>,
     
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -59,12 +67,22 @@ pub fn transfer_position(ctx: Context<TransferPosition>, market_id: u64, bin_ind
     }
     require!(total_amount > 0, RangeBetError::NoTokensToBuy);
     
-    // Initialize if needed
-    if ctx.accounts.to_position.owner == Pubkey::default() {
-        ctx.accounts.to_position.owner = ctx.accounts.to_user.key();
-        ctx.accounts.to_position.market_id = market_id;
-        ctx.accounts.to_position.bins = Vec::new();
-    }
+    let from_user_ai = ctx.accounts.from_user.to_account_info();
+    let system_program_ai = ctx.accounts.system_program.to_account_info();
+    let to_position_ai = ctx.accounts.to_position.to_account_info();
+    
+    // Initialize if needed (sender pays the rent)
+    let mut to_position = position::load_or_create(
+        &to_position_ai,
+        ctx.accounts.to_user.key(),
+        market_id,
+        ctx.bumps.to_position,
+        &from_user_ai,
+        &system_program_ai,
+    )?;
+    
+    // Net collateral moved with the tokens (the cost basis follows the per-bin ledger)
+    let mut moved_basis: u64 = 0;
     
     // Process each bin
     for i in 0..bin_indices.len() {
@@ -77,25 +95,35 @@ pub fn transfer_position(ctx: Context<TransferPosition>, market_id: u64, bin_ind
         }
         
         // Find bin in sender position and validate amount
-        let mut from_bin_found = false;
+        let mut moved: Option<(u64, u64)> = None;
         
         for bin_bal in &mut ctx.accounts.from_position.bins {
             if bin_bal.index == index {
                 require!(bin_bal.amount >= amount, RangeBetError::InsufficientTokensToTransfer);
+                
+                // Paid/received move with the tokens, pro rata to the bin balance
+                let paid = pro_rata(bin_bal.paid, amount, bin_bal.amount);
+                let received = pro_rata(bin_bal.received, amount, bin_bal.amount);
                 bin_bal.amount -= amount;
-                from_bin_found = true;
+                bin_bal.paid -= paid;
+                bin_bal.received -= received;
+                moved = Some((paid, received));
                 break;
             }
         }
         
-        require!(from_bin_found, RangeBetError::BinIndexOutOfRange);
+        let (paid, received) = moved.ok_or(error!(RangeBetError::BinIndexOutOfRange))?;
+        moved_basis = moved_basis.checked_add(paid.saturating_sub(received))
+            .ok_or(error!(RangeBetError::MathOverflow))?;
         
         // Add to recipient position
         let mut to_bin_found = false;
         
-        for bin_bal in &mut ctx.accounts.to_position.bins {
+        for bin_bal in &mut to_position.bins {
             if bin_bal.index == index {
                 bin_bal.amount += amount;
+                bin_bal.paid = bin_bal.paid.checked_add(paid).ok_or(error!(RangeBetError::MathOverflow))?;
+                bin_bal.received = bin_bal.received.checked_add(received).ok_or(error!(RangeBetError::MathOverflow))?;
                 to_bin_found = true;
                 break;
             }
@@ -103,17 +131,41 @@ pub fn transfer_position(ctx: Context<TransferPosition>, market_id: u64, bin_ind
         
         // Create new bin if not found in recipient position
         if !to_bin_found {
-            ctx.accounts.to_position.bins.push(BinBal {
+            to_position.bins.push(BinBal {
                 index,
                 amount,
+                paid,
+                received,
             });
         }
     }
     
+    // Move the cost basis of the transferred bins (never below zero)
+    let moved_basis = moved_basis.min(ctx.accounts.from_position.cost_basis);
+    ctx.accounts.from_position.cost_basis -= moved_basis;
+    to_position.cost_basis = to_position.cost_basis.checked_add(moved_basis)
+        .ok_or(error!(RangeBetError::MathOverflow))?;
+    
+    // Resize both positions to fit (sender pays for growth and receives freed rent)
+    position::fit(
+        &ctx.accounts.from_position.to_account_info(),
+        &mut ctx.accounts.from_position,
+        &from_user_ai,
+        &system_program_ai,
+    )?;
+    position::store(&to_position_ai, &mut to_position, &from_user_ai, &system_program_ai)?;
+    
     msg!("Position transfer complete: {} -> {}", 
         ctx.accounts.from_user.key(), 
         ctx.accounts.to_user.key()
     );
     
     Ok(())
-} 
\ No newline at end of file
+} 
+
+/// Share of `value` attributable to `part` out of `whole` (0 if `whole` is 0)
+fn pro_rata(value: u64, part: u64, whole: u64) -> u64 {
+    (value as u128 * part as u128)
+        .checked_div(whole as u128)
+        .unwrap_or(0) as u64
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, Market, MarketVoided, Role};
+use crate::errors::RangeBetError;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct VoidMarket<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"range-bet-state"],
+        bump,
+        constraint = program_state.has_role(Role::Resolver, &authority.key()) @ RangeBetError::ResolverOnly
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [b"market", &market_id.to_le_bytes()],
+        bump,
+        constraint = !market.closed @ RangeBetError::MarketClosed
+    )]
+    pub market: Account<'info, Market>,
+}
+
+pub fn void_market(
+    ctx: Context<VoidMarket>,
+    market_id: u64,
+    refund_deadline: i64,
+) -> Result<()> {
+    require!(
+        refund_deadline > Clock::get()?.unix_timestamp,
+        RangeBetError::InvalidRefundDeadline
+    );
+
+    let market = &mut ctx.accounts.market;
+    let program_state = &mut ctx.accounts.program_state;
+
+    // Stop trading and mark voided
+    market.active = false;
+    market.closed = true;
+    market.voided = true;
+    market.refund_deadline = refund_deadline;
+    market.proposed_bin = None;
+
+    // Snapshot the pool; refunds are paid pro rata to cost basis, never above it
+    // (collateral beyond the total cost basis, e.g. a rolled-over pool, stays withdrawable)
+    market.payout_pool = market.collateral_balance.min(market.total_cost_basis);
+    market.winning_supply = market.total_cost_basis;
+    market.remaining_supply = market.total_cost_basis;
+    market.remaining_payout = market.payout_pool;
+
+    // Update program state - closed market counter
+    program_state.closed_market_count += 1;
+
+    // Emit event
+    emit!(MarketVoided {
+        market_id,
+        refund_deadline,
+    });
+
+    msg!("Market voided: ID = {}, Refund deadline = {}", market_id, refund_deadline);
+
+    Ok(())
+}
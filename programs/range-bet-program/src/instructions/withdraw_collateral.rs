@@ -1,18 +1,18 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, TokenAccount, Token, Transfer};
-use crate::state::{ProgramState, CollateralOut, Market};
+use crate::state::{ProgramState, CollateralOut, Market, NoWinnerPolicy, Role};
 use crate::errors::RangeBetError;
 
 #[derive(Accounts)]
 #[instruction(market_id: u64)]
 pub struct WithdrawCollateral<'info> {
     #[account(mut)]
-    pub owner: Signer<'info>,
+    pub authority: Signer<'info>,
     
     #[account(
         seeds = [b"range-bet-state"],
         bump,
-        constraint = program_state.owner == owner.key() @ RangeBetError::OwnerOnly
+        constraint = program_state.has_role(Role::Treasurer, &authority.key()) @ RangeBetError::TreasurerOnly
     )]
     pub program_state: Account<'info, ProgramState>,
     
@@ -24,10 +24,19 @@ pub struct WithdrawCollateral<'info> {
     )]
     pub market: Account<'info, Market>,
     
-    #[account(mut)]
-    pub owner_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint,
+        constraint = treasury_token_account.owner == program_state.treasury @ RangeBetError::TreasurerOnly
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        address = market.vault @ RangeBetError::InvalidVault,
+        constraint = vault.mint == market.collateral_mint @ RangeBetError::InvalidCollateralMint,
+        constraint = vault.owner == vault_authority.key() @ RangeBetError::InvalidVault
+    )]
     pub vault: Account<'info, TokenAccount>,
     
     /// Vault authority PDA (program-signing PDA)
@@ -38,6 +47,14 @@ pub struct WithdrawCollateral<'info> {
     /// CHECK: Not an actual account, used as PDA
     pub vault_authority: UncheckedAccount<'info>,
     
+    /// Market receiving a rolled-over no-winner pool: any open market with the same collateral mint
+    #[account(mut)]
+    pub next_market: Option<Account<'info, Market>>,
+    
+    /// Vault of `next_market` (required when rolling over a no-winner pool)
+    #[account(mut)]
+    pub next_vault: Option<Account<'info, TokenAccount>>,
+    
     pub token_program: Program<'info, Token>,
 }
 
@@ -45,13 +62,71 @@ pub fn withdraw_collateral(
     ctx: Context<WithdrawCollateral>,
     market_id: u64,
 ) -> Result<()> {
-    // Amount to withdraw (market collateral balance)
-    let amount = ctx.accounts.market.collateral_balance;
+    let now = Clock::get()?.unix_timestamp;
+    let market = &ctx.accounts.market;
+    
+    // Collateral owed to winners (or refunds) stays in the vault until the claim deadline
+    let owed = market.owed_payout();
+    let residual = market.collateral_balance.checked_sub(owed)
+        .ok_or(error!(RangeBetError::MathUnderflow))?;
+    let unclaimed = if market.claims_expired(now) { owed } else { 0 };
+    
+    // Payouts cannot be forfeited while users are unable to claim them
+    require!(
+        unclaimed == 0 || !ctx.accounts.program_state.claims_paused(),
+        RangeBetError::ClaimsPaused
+    );
+    
+    // Amount to withdraw
+    let amount = residual.checked_add(unclaimed).ok_or(error!(RangeBetError::MathOverflow))?;
+    
+    // Refunds and winnings must settle first unless their deadline has passed
+    if amount == 0 && owed > 0 {
+        let is_refund = market.voided
+            || (market.has_no_winner() && market.no_winner_policy == NoWinnerPolicy::Refund);
+        if is_refund {
+            return err!(RangeBetError::RefundsOutstanding);
+        }
+        return err!(RangeBetError::ClaimsOutstanding);
+    }
     
     // Check if there is an amount to withdraw
     require!(amount > 0, RangeBetError::NoCollateralToWithdraw);
     
-    // Transfer tokens (vault -> owner)
+    // Apply the no-winner policy to the destination
+    let no_winner_policy = if market.has_no_winner() {
+        Some(market.no_winner_policy)
+    } else {
+        None
+    };
+    
+    let (destination, recipient) = match no_winner_policy {
+        Some(NoWinnerPolicy::RollOver) => {
+            let next_market = ctx.accounts.next_market.as_mut()
+                .ok_or(error!(RangeBetError::MissingNextMarket))?;
+            let next_vault = ctx.accounts.next_vault.as_ref()
+                .ok_or(error!(RangeBetError::MissingNextMarket))?;
+            
+            // The treasurer picks the receiving market, since the following ID may already be
+            // resolved or finalized while markets run in parallel; it only has to still be open
+            require!(!next_market.closed, RangeBetError::MarketClosed);
+            require!(next_vault.key() == next_market.vault, RangeBetError::InvalidVault);
+            require!(
+                next_market.collateral_mint == ctx.accounts.market.collateral_mint,
+                RangeBetError::InvalidCollateralMint
+            );
+            
+            next_market.collateral_balance = next_market.collateral_balance.checked_add(amount)
+                .ok_or(error!(RangeBetError::MathOverflow))?;
+            (next_vault.to_account_info(), next_market.key())
+        }
+        Some(NoWinnerPolicy::Refund) | Some(NoWinnerPolicy::Treasury) | None => {
+            // Refund pools only reach here once refunds are settled or expired
+            (ctx.accounts.treasury_token_account.to_account_info(), ctx.accounts.program_state.treasury)
+        }
+    };
+    
+    // Transfer tokens (vault -> treasury or next market)
     let vault_authority_bump = ctx.bumps.vault_authority;
     let market_id_bytes = market_id.to_le_bytes();
     
@@ -66,7 +141,7 @@ pub fn withdraw_collateral(
     
     let cpi_accounts = Transfer {
         from: ctx.accounts.vault.to_account_info(),
-        to: ctx.accounts.owner_token_account.to_account_info(),
+        to: destination,
         authority: ctx.accounts.vault_authority.to_account_info(),
     };
     
@@ -78,19 +153,28 @@ pub fn withdraw_collateral(
     
     token::transfer(cpi_ctx, amount)?;
     
-    // Set market collateral balance to 0
-    ctx.accounts.market.collateral_balance = 0;
+    // Update market collateral; swept payouts can no longer be claimed
+    let market = &mut ctx.accounts.market;
+    market.collateral_balance = market.collateral_balance.checked_sub(amount)
+        .ok_or(error!(RangeBetError::MathUnderflow))?;
+    if unclaimed > 0 {
+        market.remaining_supply = 0;
+        market.remaining_payout = 0;
+    }
     
     // Emit event
     emit!(CollateralOut {
-        to: ctx.accounts.owner.key(),
+        to: recipient,
         amount,
+        residual,
+        unclaimed,
     });
     
     msg!(
-        "Collateral withdrawn: target = {}, amount = {}", 
-        ctx.accounts.owner.key(),
-        amount
+        "Collateral withdrawn: target = {}, amount = {}, unclaimed swept = {}", 
+        recipient,
+        amount,
+        unclaimed
     );
     
     Ok(())
@@ -6,8 +6,11 @@ declare_id!("97i8BgDJG6yZggN2Di5UnERs6X5PqYqnkSvkMdvw1d5J");
 mod state;
 mod errors;
 mod instructions;
+mod oracle;
+mod position;
 
 use instructions::*;
+use state::Role;
 
 #[program]
 pub mod range_bet_program {
@@ -20,13 +23,18 @@ pub mod range_bet_program {
 
     pub fn create_market(
         ctx: Context<CreateMarket>,
-        tick_spacing: u32,
-        min_tick: i64,
-        max_tick: i64,
-        close_ts: i64,
+        params: CreateMarketParams,
     ) -> Result<()> {
         msg!("Create Market");
-        instructions::create_market::create_market(ctx, tick_spacing, min_tick, max_tick, close_ts)
+        instructions::create_market::create_market(ctx, params)
+    }
+
+    pub fn allocate_market_bins(
+        ctx: Context<AllocateMarketBins>,
+        market_id: u64,
+    ) -> Result<()> {
+        msg!("Allocate Market Bins");
+        instructions::allocate_market_bins::allocate_market_bins(ctx, market_id)
     }
 
     pub fn buy_tokens(
@@ -40,13 +48,90 @@ pub mod range_bet_program {
         instructions::buy_tokens::buy_tokens(ctx, _market_id, bin_indices, amounts, max_collateral)
     }
 
-    pub fn close_market(
-        ctx: Context<CloseMarket>,
+    pub fn buy_range(
+        ctx: Context<BuyTokens>,
+        market_id: u64,
+        lower_tick: i64,
+        upper_tick: i64,
+        amount_per_bin: u64,
+        max_collateral: u64,
+    ) -> Result<()> {
+        msg!("Buy Range");
+        instructions::buy_tokens::buy_range(ctx, market_id, lower_tick, upper_tick, amount_per_bin, max_collateral)
+    }
+
+    pub fn buy_with_budget(
+        ctx: Context<BuyWithBudget>,
+        market_id: u64,
+        bin_indices: Vec<u16>,
+        budget: u64,
+        min_tokens_per_bin: u64,
+    ) -> Result<()> {
+        msg!("Buy With Budget");
+        instructions::buy_with_budget::buy_with_budget(ctx, market_id, bin_indices, budget, min_tokens_per_bin)
+    }
+
+    pub fn sell_tokens(
+        ctx: Context<SellTokens>,
+        market_id: u64,
+        bin_indices: Vec<u16>,
+        amounts: Vec<u64>,
+        min_collateral_out: u64,
+    ) -> Result<()> {
+        msg!("Sell Tokens");
+        instructions::sell_tokens::sell_tokens(ctx, market_id, bin_indices, amounts, min_collateral_out)
+    }
+
+    pub fn propose_resolution(
+        ctx: Context<ProposeResolution>,
         market_id: u64,
         winning_bin: u16,
     ) -> Result<()> {
-        msg!("Close Market");
-        instructions::close_market::close_market(ctx, market_id, winning_bin)
+        msg!("Propose Resolution");
+        instructions::propose_resolution::propose_resolution(ctx, market_id, winning_bin)
+    }
+
+    pub fn propose_resolution_at_tick(
+        ctx: Context<ProposeResolution>,
+        market_id: u64,
+        winning_tick: i64,
+    ) -> Result<()> {
+        msg!("Propose Resolution At Tick");
+        instructions::propose_resolution::propose_resolution_at_tick(ctx, market_id, winning_tick)
+    }
+
+    pub fn override_resolution(
+        ctx: Context<OverrideResolution>,
+        market_id: u64,
+        winning_bin: u16,
+    ) -> Result<()> {
+        msg!("Override Resolution");
+        instructions::override_resolution::override_resolution(ctx, market_id, winning_bin)
+    }
+
+    pub fn override_resolution_at_tick(
+        ctx: Context<OverrideResolution>,
+        market_id: u64,
+        winning_tick: i64,
+    ) -> Result<()> {
+        msg!("Override Resolution At Tick");
+        instructions::override_resolution::override_resolution_at_tick(ctx, market_id, winning_tick)
+    }
+
+    pub fn finalize_resolution(
+        ctx: Context<FinalizeResolution>,
+        market_id: u64,
+    ) -> Result<()> {
+        msg!("Finalize Resolution");
+        instructions::finalize_resolution::finalize_resolution(ctx, market_id)
+    }
+
+    pub fn resolve_from_oracle(
+        ctx: Context<ResolveFromOracle>,
+        market_id: u64,
+    ) -> Result<()> {
+        msg!("Resolve From Oracle");
+        instructions::resolve_from_oracle::resolve_from_oracle(ctx, market_id)
     }
 
     pub fn claim_reward(
@@ -65,6 +150,15 @@ pub mod range_bet_program {
         instructions::toggle_market_status::toggle_market_status(ctx, _market_id, active)
     }
 
+    pub fn set_paused(
+        ctx: Context<SetPaused>,
+        paused: bool,
+        claims_open: bool,
+    ) -> Result<()> {
+        msg!("Set Paused");
+        instructions::set_paused::set_paused(ctx, paused, claims_open)
+    }
+
     pub fn withdraw_collateral(
         ctx: Context<WithdrawCollateral>,
         market_id: u64,
@@ -82,4 +176,136 @@ pub mod range_bet_program {
         msg!("Transfer Position");
         instructions::transfer_position::transfer_position(ctx, market_id, bin_indices, amounts)
     }
-}
+
+    pub fn void_market(
+        ctx: Context<VoidMarket>,
+        market_id: u64,
+        refund_deadline: i64,
+    ) -> Result<()> {
+        msg!("Void Market");
+        instructions::void_market::void_market(ctx, market_id, refund_deadline)
+    }
+
+    pub fn refund_position(
+        ctx: Context<RefundPosition>,
+    ) -> Result<()> {
+        msg!("Refund Position");
+        instructions::refund_position::refund_position(ctx)
+    }
+
+    pub fn extend_close_ts(
+        ctx: Context<ExtendCloseTs>,
+        market_id: u64,
+        new_close_ts: i64,
+    ) -> Result<()> {
+        msg!("Extend Close Time");
+        instructions::extend_close_ts::extend_close_ts(ctx, market_id, new_close_ts)
+    }
+
+    pub fn close_position(
+        ctx: Context<ClosePosition>,
+    ) -> Result<()> {
+        msg!("Close Position");
+        instructions::close_position::close_position(ctx)
+    }
+
+    pub fn close_positions<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClosePositions<'info>>,
+    ) -> Result<()> {
+        msg!("Close Positions");
+        instructions::close_position::close_positions(ctx)
+    }
+
+    pub fn finalize_market(
+        ctx: Context<FinalizeMarket>,
+        market_id: u64,
+    ) -> Result<()> {
+        msg!("Finalize Market");
+        instructions::finalize_market::finalize_market(ctx, market_id)
+    }
+
+    pub fn settle_bond(
+        ctx: Context<SettleBond>,
+        market_id: u64,
+    ) -> Result<()> {
+        msg!("Settle Bond");
+        instructions::settle_bond::settle_bond(ctx, market_id)
+    }
+
+    pub fn set_default_fee(
+        ctx: Context<SetDefaultFee>,
+        default_fee_bps: u16,
+    ) -> Result<()> {
+        msg!("Set Default Fee");
+        instructions::fee_config::set_default_fee(ctx, default_fee_bps)
+    }
+
+    pub fn set_creator_config(
+        ctx: Context<SetCreatorConfig>,
+        creation_bond: u64,
+        creator_fee_bps: u16,
+        min_dispute_window: i64,
+        min_claim_window: i64,
+    ) -> Result<()> {
+        msg!("Set Creator Config");
+        instructions::fee_config::set_creator_config(
+            ctx,
+            creation_bond,
+            creator_fee_bps,
+            min_dispute_window,
+            min_claim_window,
+        )
+    }
+
+    pub fn set_market_fee(
+        ctx: Context<SetMarketFee>,
+        market_id: u64,
+        fee_bps: u16,
+    ) -> Result<()> {
+        msg!("Set Market Fee");
+        instructions::fee_config::set_market_fee(ctx, market_id, fee_bps)
+    }
+
+    pub fn collect_fees(
+        ctx: Context<CollectFees>,
+        market_id: u64,
+    ) -> Result<()> {
+        msg!("Collect Fees");
+        instructions::collect_fees::collect_fees(ctx, market_id)
+    }
+
+    pub fn grant_role(
+        ctx: Context<ManageRoles>,
+        role: Role,
+        account: Pubkey,
+    ) -> Result<()> {
+        msg!("Grant Role");
+        instructions::manage_roles::grant_role(ctx, role, account)
+    }
+
+    pub fn revoke_role(
+        ctx: Context<ManageRoles>,
+        role: Role,
+    ) -> Result<()> {
+        msg!("Revoke Role");
+        instructions::manage_roles::revoke_role(ctx, role)
+    }
+
+    pub fn propose_owner(
+        ctx: Context<ProposeOwner>,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        msg!("Propose Owner");
+        instructions::transfer_ownership::propose_owner(ctx, new_owner)
+    }
+
+    pub fn cancel_ownership_transfer(ctx: Context<ProposeOwner>) -> Result<()> {
+        msg!("Cancel Ownership Transfer");
+        instructions::transfer_ownership::cancel_ownership_transfer(ctx)
+    }
+
+    pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+        msg!("Accept Ownership");
+        instructions::transfer_ownership::accept_ownership(ctx)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, OracleFormat};
+use crate::errors::RangeBetError;
+
+// Pyth legacy price account layout
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const PYTH_PRICE_ACCOUNT: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const PYTH_MAGIC_OFFSET: usize = 0;
+const PYTH_ATYPE_OFFSET: usize = 8;
+const PYTH_EXPO_OFFSET: usize = 20;
+const PYTH_TIMESTAMP_OFFSET: usize = 96;
+const PYTH_PRICE_OFFSET: usize = 208;
+const PYTH_CONF_OFFSET: usize = 216;
+const PYTH_STATUS_OFFSET: usize = 224;
+const PYTH_MIN_LEN: usize = 228;
+
+/// Price read from a feed account
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+/// Read the current aggregate price from a feed account
+pub fn read_price(format: OracleFormat, data: &[u8]) -> Result<OraclePrice> {
+    match format {
+        OracleFormat::PythLegacy => read_pyth_legacy(data),
+    }
+}
+
+fn read_pyth_legacy(data: &[u8]) -> Result<OraclePrice> {
+    require!(data.len() >= PYTH_MIN_LEN, RangeBetError::InvalidOracleAccount);
+    require!(
+        read_u32(data, PYTH_MAGIC_OFFSET) == PYTH_MAGIC
+            && read_u32(data, PYTH_ATYPE_OFFSET) == PYTH_PRICE_ACCOUNT,
+        RangeBetError::InvalidOracleAccount
+    );
+
+    // Only a trading price is a valid aggregate
+    require!(
+        read_u32(data, PYTH_STATUS_OFFSET) == PYTH_STATUS_TRADING,
+        RangeBetError::OraclePriceUnavailable
+    );
+
+    Ok(OraclePrice {
+        price: read_i64(data, PYTH_PRICE_OFFSET),
+        conf: read_i64(data, PYTH_CONF_OFFSET) as u64,
+        expo: read_u32(data, PYTH_EXPO_OFFSET) as i32,
+        publish_time: read_i64(data, PYTH_TIMESTAMP_OFFSET),
+    })
+}
+
+/// Bin containing `tick` (see `Market::bin_for_tick`), rejecting prices outside a market without tail bins
+pub fn tick_to_bin(market: &Market, tick: i64) -> Result<u16> {
+    market.bin_for_tick(tick).map_err(|_| error!(RangeBetError::OraclePriceOutOfRange))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_i64(data: &[u8], offset: usize) -> i64 {
+    i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
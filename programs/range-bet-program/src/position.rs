@@ -0,0 +1,179 @@
+use anchor_lang::{prelude::*, system_program};
+use crate::state::UserMarketPosition;
+use crate::errors::RangeBetError;
+
+/// Load the position at `position_ai`, creating an empty one for `owner` if the account does not exist yet
+/// `bump` is the bump of the position PDA; `payer` funds the rent of a new account
+pub fn load_or_create<'info>(
+    position_ai: &AccountInfo<'info>,
+    owner: Pubkey,
+    market_id: u64,
+    bump: u8,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<UserMarketPosition> {
+    // Existing position
+    if position_ai.owner == &crate::ID {
+        let position = UserMarketPosition::try_deserialize(&mut &position_ai.try_borrow_data()?[..])?;
+        require_keys_eq!(position.owner, owner, RangeBetError::OwnerOnly);
+        return Ok(position);
+    }
+
+    let market_id_bytes = market_id.to_le_bytes();
+    let seeds = &[
+        b"pos" as &[u8],
+        owner.as_ref(),
+        &market_id_bytes as &[u8],
+        &[bump]
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // New positions start with room for no bins and grow as bins are added
+    let space = UserMarketPosition::space(0);
+    let needed_lamports = Rent::get()?.minimum_balance(space);
+    let current_lamports = position_ai.lamports();
+
+    if current_lamports == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: position_ai.clone(),
+                },
+                signer_seeds,
+            ),
+            needed_lamports,
+            space as u64,
+            &crate::ID,
+        )?;
+    } else {
+        // Address was pre-funded: top up, allocate and assign instead of creating
+        if needed_lamports > current_lamports {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer {
+                        from: payer.clone(),
+                        to: position_ai.clone(),
+                    },
+                ),
+                needed_lamports - current_lamports,
+            )?;
+        }
+
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Allocate {
+                    account_to_allocate: position_ai.clone(),
+                },
+                signer_seeds,
+            ),
+            space as u64,
+        )?;
+
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Assign {
+                    account_to_assign: position_ai.clone(),
+                },
+                signer_seeds,
+            ),
+            &crate::ID,
+        )?;
+    }
+
+    Ok(UserMarketPosition {
+        owner,
+        market_id,
+        cost_basis: 0,
+        closed_paid: 0,
+        closed_received: 0,
+        bins: Vec::new(),
+    })
+}
+
+/// Drop empty bins and resize the position account to fit
+/// `payer` pays for growth and is refunded the rent freed by shrinking
+pub fn fit<'info>(
+    position_ai: &AccountInfo<'info>,
+    position: &mut UserMarketPosition,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    // Compact zero-amount entries, keeping their ledger in the position totals
+    for bin_bal in position.bins.iter().filter(|bin_bal| bin_bal.amount == 0) {
+        position.closed_paid = position.closed_paid.checked_add(bin_bal.paid)
+            .ok_or(error!(RangeBetError::MathOverflow))?;
+        position.closed_received = position.closed_received.checked_add(bin_bal.received)
+            .ok_or(error!(RangeBetError::MathOverflow))?;
+    }
+    position.bins.retain(|bin_bal| bin_bal.amount > 0);
+
+    let new_size = UserMarketPosition::space(position.bins.len());
+    if new_size == position_ai.data_len() {
+        return Ok(());
+    }
+
+    let needed_lamports = Rent::get()?.minimum_balance(new_size);
+    let current_lamports = position_ai.lamports();
+
+    if needed_lamports > current_lamports {
+        // Growing: payer covers the rent difference
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: position_ai.clone(),
+                },
+            ),
+            needed_lamports - current_lamports,
+        )?;
+    }
+
+    position_ai.realloc(new_size, false)?;
+
+    if current_lamports > needed_lamports {
+        // Shrinking: excess rent goes back to the payer
+        let refund = current_lamports - needed_lamports;
+        **position_ai.try_borrow_mut_lamports()? -= refund;
+        **payer.try_borrow_mut_lamports()? += refund;
+    }
+
+    Ok(())
+}
+
+/// Fit the position account to `position` and write it back
+pub fn store<'info>(
+    position_ai: &AccountInfo<'info>,
+    position: &mut UserMarketPosition,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    fit(position_ai, position, payer, system_program)?;
+
+    let mut data = position_ai.try_borrow_mut_data()?;
+    position.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+/// Close the position account, sending its rent to `destination`
+/// @return Lamports returned
+pub fn close<'info>(position_ai: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<u64> {
+    let lamports = position_ai.lamports();
+    let destination_lamports = destination.lamports()
+        .checked_add(lamports)
+        .ok_or(error!(RangeBetError::MathOverflow))?;
+
+    **destination.try_borrow_mut_lamports()? = destination_lamports;
+    **position_ai.try_borrow_mut_lamports()? = 0;
+
+    position_ai.assign(&system_program::ID);
+    position_ai.realloc(0, false)?;
+
+    Ok(lamports)
+}
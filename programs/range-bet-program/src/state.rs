@@ -1,11 +1,65 @@
 use anchor_lang::prelude::*;
+use crate::errors::RangeBetError;
+use range_bet_math_core::ticks;
 
 /// Global configuration and metadata storage
 #[account]
 pub struct ProgramState {
     pub owner: Pubkey,           // Program owner (admin)
     pub market_count: u64,       // Market ID sequence (auto-increment)
-    pub last_closed_market: Option<u64>, // Most recently closed market ID (None = not yet closed)
+    pub closed_market_count: u64, // Number of resolved (closed or voided) markets
+    pub market_creator: Pubkey,  // Market creator role (creates, extends and finalizes markets)
+    pub resolver: Pubkey,        // Resolver role (proposes, overrides and voids resolutions)
+    pub pauser: Pubkey,          // Pauser role (activates and deactivates markets, pauses the program)
+    pub treasury: Pubkey,        // Treasurer role (withdraws collateral, collects fees, receives swept dust)
+    pub default_fee_bps: u16,    // Protocol fee applied to new markets (basis points)
+    pub pending_owner: Pubkey,   // Proposed new owner (default key when no transfer is pending)
+    pub paused: bool,            // Global pause: halts buys, sells and transfers in every market
+    pub claims_open_while_paused: bool, // Claims and refunds stay open during a pause (trading-only pause)
+    pub creation_bond: u64,      // Bond posted by creators without the market creator role (0 = permissionless creation disabled)
+    pub creator_fee_bps: u16,    // Share of a bonded market's fees paid to its creator (basis points of the fee)
+    pub min_dispute_window: i64, // Shortest dispute window a bonded creator may set (seconds)
+    pub min_claim_window: i64,   // Shortest claim deadline a bonded creator may set (seconds, 0 = no deadline is always allowed)
+}
+
+impl ProgramState {
+    /// Highest protocol fee a market can charge (10%)
+    pub const MAX_FEE_BPS: u16 = 1_000;
+    
+    /// Account currently holding `role`
+    pub fn role_holder(&self, role: Role) -> Pubkey {
+        match role {
+            Role::MarketCreator => self.market_creator,
+            Role::Resolver => self.resolver,
+            Role::Pauser => self.pauser,
+            Role::Treasurer => self.treasury,
+        }
+    }
+    
+    /// Set the account holding `role`
+    pub fn set_role_holder(&mut self, role: Role, holder: Pubkey) {
+        match role {
+            Role::MarketCreator => self.market_creator = holder,
+            Role::Resolver => self.resolver = holder,
+            Role::Pauser => self.pauser = holder,
+            Role::Treasurer => self.treasury = holder,
+        }
+    }
+    
+    /// Whether buys, sells and position transfers are halted
+    pub fn trading_paused(&self) -> bool {
+        self.paused
+    }
+    
+    /// Whether claims and refunds are halted
+    pub fn claims_paused(&self) -> bool {
+        self.paused && !self.claims_open_while_paused
+    }
+    
+    /// Whether `key` may act as `role` (the owner may act as every role)
+    pub fn has_role(&self, role: Role, key: &Pubkey) -> bool {
+        *key == self.owner || *key == self.role_holder(role)
+    }
 }
 
 /// Market state structure
@@ -16,16 +70,258 @@ pub struct Market {
     pub tick_spacing: u32,
     pub min_tick: i64,
     pub max_tick: i64,
+    pub tail_bins: bool,        // Last two bins are open-ended tails (< min_tick, > max_tick)
+    pub bin_count: u32,         // Number of bins, including tail bins (quantities live in MarketBins)
     pub t_total: u64,           // Total sum of all bin tokens (T)
     pub collateral_balance: u64,
+    pub collateral_mint: Pubkey, // Collateral token mint
+    pub vault: Pubkey,          // Collateral vault (ATA owned by the vault authority PDA)
+    pub creator: Pubkey,        // Paid the market's rent; refunded when the market is finalized
     pub winning_bin: Option<u16>,  // Winning bin index (determined at close, None=undetermined)
     pub open_ts: i64,           // When the market was opened
     pub close_ts: i64,          // When the market is scheduled to close
+    pub trading_cutoff: i64,    // Seconds before close_ts at which trading stops (0 = at close_ts)
+    pub series_previous: Option<u64>, // Sequential series: market that must be resolved before this one
+    pub no_winner_policy: NoWinnerPolicy, // What happens to the pool if the winning bin is empty
+    pub oracle: Option<OracleConfig>, // Price feed used by resolve_from_oracle (None=owner resolution only)
+    pub dispute_window: i64,    // Seconds a proposed resolution can be overridden before finalization
+    pub proposed_bin: Option<u16>, // Pending winning bin awaiting finalization (None=not proposed)
+    pub challenge_deadline: i64, // Proposed bin can be overridden until this time
+    pub total_cost_basis: u64,  // Sum of all positions' cost basis
+    pub fee_bps: u16,           // Protocol fee on buys and sells (basis points)
+    pub fees_accrued: u64,      // Fees held in the vault, not part of collateral_balance
+    pub creator_fee_bps: u16,   // Creator's share of collected fees (basis points of the fee)
+    pub bond: u64,              // Creator bond held in the vault until settle_bond
+    
+    // Payout snapshot (frozen at close)
+    pub payout_pool: u64,       // Collateral balance at close, distributed to winners
+    pub winning_supply: u64,    // Winning bin token quantity at close (total cost basis for refunds)
+    pub remaining_supply: u64,  // Winning tokens not yet claimed
+    pub remaining_payout: u64,  // Payout pool not yet claimed
+    pub no_winner: bool,        // Winning bin held no tokens at close
+    pub claim_window: i64,      // Seconds after resolution during which payouts can be claimed (0 = no deadline)
+    pub claim_deadline: i64,    // After this, unclaimed payouts can be swept (0 = no deadline)
+    
+    // Voiding
+    pub voided: bool,           // Market was cancelled; positions are refunded at cost
+    pub refund_deadline: i64,   // After this, refunds end and collateral can be withdrawn
+}
+
+impl Market {
+    /// Whether buys and sells are accepted at `now`
+    pub fn is_trading_open(&self, now: i64) -> bool {
+        now < self.close_ts.saturating_sub(self.trading_cutoff)
+    }
+    
+    /// Number of bins covering min_tick..=max_tick
+    pub fn range_bin_count(&self) -> usize {
+        if self.tail_bins {
+            self.bin_count as usize - 2
+        } else {
+            self.bin_count as usize
+        }
+    }
+    
+    /// Bin for outcomes below min_tick, if the market has tail bins
+    pub fn underflow_bin(&self) -> Option<u16> {
+        self.tail_bins.then(|| self.range_bin_count() as u16)
+    }
+    
+    /// Bin for outcomes above max_tick, if the market has tail bins
+    pub fn overflow_bin(&self) -> Option<u16> {
+        self.tail_bins.then(|| self.range_bin_count() as u16 + 1)
+    }
+    
+    /// Bin containing an outcome tick; ticks outside the market go to the tail bins
+    pub fn bin_for_tick(&self, tick: i64) -> Result<u16> {
+        ticks::outcome_to_index(tick, self.min_tick, self.max_tick, self.tick_spacing, self.tail_bins)
+    }
+    
+    /// Protocol fee charged on `amount` of trade collateral, rounded up in the protocol's favor
+    pub fn fee_for(&self, amount: u64) -> u64 {
+        (amount as u128 * self.fee_bps as u128).div_ceil(10_000) as u64
+    }
     
-    // Fixed offset array for bin storage
-    // Index is calculated as (bin_index - min_tick) / tick_spacing
-    // Values represent token quantity (q) in each bin
-    pub bins: Vec<u64>,
+    /// Creator's share of `fees` collected from the market
+    pub fn creator_fee_for(&self, fees: u64) -> u64 {
+        (fees as u128 * self.creator_fee_bps as u128 / 10_000) as u64
+    }
+    
+    /// Whether the market closed on a bin nobody holds tokens in
+    pub fn has_no_winner(&self) -> bool {
+        self.closed && self.winning_bin.is_some() && self.no_winner
+    }
+    
+    /// Whether payouts (or refunds, for voided markets) can no longer be claimed at `now`
+    pub fn claims_expired(&self, now: i64) -> bool {
+        if self.voided {
+            now > self.refund_deadline
+        } else {
+            self.claim_deadline > 0 && now > self.claim_deadline
+        }
+    }
+    
+    /// Collateral reserved for payouts that have not been claimed yet
+    pub fn owed_payout(&self) -> u64 {
+        if self.remaining_supply > 0 {
+            self.remaining_payout
+        } else {
+            0
+        }
+    }
+    
+    /// Whether `position` can still claim a payout or refund at `now`
+    pub fn has_claim(&self, position: &UserMarketPosition, now: i64) -> bool {
+        if self.claims_expired(now) {
+            return false;
+        }
+        
+        if self.voided || (self.has_no_winner() && self.no_winner_policy == NoWinnerPolicy::Refund) {
+            return position.cost_basis > 0;
+        }
+        
+        match self.winning_bin {
+            Some(winning_bin) => position.bins
+                .iter()
+                .any(|bin_bal| bin_bal.index == winning_bin && bin_bal.amount > 0),
+            None => false,
+        }
+    }
+    
+    /// Payout owed for `amount` units of the snapshot supply
+    pub fn payout_for(&self, amount: u64) -> u64 {
+        if amount >= self.remaining_supply {
+            // Last claimer receives exactly the residual
+            self.remaining_payout
+        } else {
+            // (amount / winning supply) * payout pool
+            let payout = (amount as u128 * self.payout_pool as u128)
+                / self.winning_supply as u128;
+            
+            // Never pay out more than what is left
+            (payout as u64).min(self.remaining_payout)
+        }
+    }
+    
+    /// Record a payout of `payout` for `amount` units of the snapshot supply
+    pub fn record_payout(&mut self, amount: u64, payout: u64) -> Result<()> {
+        self.collateral_balance = self.collateral_balance.checked_sub(payout)
+            .ok_or(error!(RangeBetError::MathUnderflow))?;
+        self.remaining_supply = self.remaining_supply.saturating_sub(amount);
+        self.remaining_payout -= payout;
+        Ok(())
+    }
+}
+
+/// Bin quantity storage for a market (zero-copy, grown incrementally)
+///
+/// The header is followed by `allocated` little-endian u64 token quantities (q).
+/// Index is calculated as (bin_index - min_tick) / tick_spacing;
+/// with tail bins, the underflow and overflow bins follow the in-range bins.
+#[account(zero_copy)]
+pub struct MarketBins {
+    pub market_id: u64,
+    pub allocated: u32,         // Bins with storage allocated so far
+    pub padding: [u8; 4],
+}
+
+impl MarketBins {
+    /// Bins that can be allocated in one instruction (stays under the 10 KiB realloc limit)
+    pub const MAX_BINS_PER_ALLOCATION: usize = 1024;
+    
+    /// Offset of the first bin quantity in the account data
+    pub const DATA_OFFSET: usize = 8 + std::mem::size_of::<MarketBins>();
+    
+    // Discriminator + market_id (read raw, as the discriminator is only written on exit after init)
+    const ALLOCATED_OFFSET: usize = 16;
+    
+    /// Account size with storage for `allocated` bins
+    pub fn space(allocated: usize) -> usize {
+        Self::DATA_OFFSET + 8 * allocated
+    }
+    
+    /// Fail unless storage for all `bin_count` bins has been allocated
+    pub fn require_allocated(data: &[u8], bin_count: u32) -> Result<()> {
+        require!(Self::allocated(data) == bin_count as usize, RangeBetError::BinsNotAllocated);
+        Ok(())
+    }
+    
+    /// Token quantity in bin `index`
+    pub fn get(data: &[u8], index: u16) -> Result<u64> {
+        let offset = Self::offset(data, index)?;
+        Ok(u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap()))
+    }
+    
+    /// Token quantity in bin `index`, 0 for bins not allocated yet (trading needs every bin allocated)
+    pub fn get_or_zero(data: &[u8], index: u16) -> u64 {
+        Self::get(data, index).unwrap_or(0)
+    }
+    
+    /// Set the token quantity in bin `index`
+    pub fn set(data: &mut [u8], index: u16, q: u64) -> Result<()> {
+        let offset = Self::offset(data, index)?;
+        data[offset..offset + 8].copy_from_slice(&q.to_le_bytes());
+        Ok(())
+    }
+    
+    /// Number of bins with storage allocated
+    pub fn allocated(data: &[u8]) -> usize {
+        u32::from_le_bytes(
+            data[Self::ALLOCATED_OFFSET..Self::ALLOCATED_OFFSET + 4].try_into().unwrap()
+        ) as usize
+    }
+    
+    /// Record the number of bins with storage allocated
+    pub fn set_allocated(data: &mut [u8], allocated: u32) {
+        data[Self::ALLOCATED_OFFSET..Self::ALLOCATED_OFFSET + 4].copy_from_slice(&allocated.to_le_bytes());
+    }
+    
+    fn offset(data: &[u8], index: u16) -> Result<usize> {
+        require!((index as usize) < Self::allocated(data), RangeBetError::BinIndexOutOfRange);
+        Ok(Self::DATA_OFFSET + 8 * index as usize)
+    }
+}
+
+/// Administrative roles that can be granted separately from the owner
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// Creates markets, allocates their bins, extends close times and finalizes settled markets
+    MarketCreator,
+    /// Proposes, overrides and voids resolutions
+    Resolver,
+    /// Activates and deactivates markets and pauses the whole program
+    Pauser,
+    /// Withdraws collateral and collects fees into the treasury
+    Treasurer,
+}
+
+/// Policy applied to the pool when the winning bin holds no tokens
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NoWinnerPolicy {
+    /// Refund all bettors pro rata to their cost basis
+    Refund,
+    /// Roll the pool into another open market chosen at withdrawal
+    RollOver,
+    /// Send the pool to the treasury (withdrawn by the treasurer)
+    Treasury,
+}
+
+/// Price feed configuration for oracle resolution
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OracleConfig {
+    pub feed: Pubkey,            // Price feed account
+    pub feed_owner: Pubkey,      // Program that must own the price feed account
+    pub format: OracleFormat,    // Layout of the price feed account
+    pub tick_expo: i32,          // Decimal exponent of one tick (tick = price / 10^tick_expo)
+    pub max_conf_bps: u16,       // Maximum confidence interval relative to price, in basis points
+    pub max_publish_delay: i64,  // Price must be published within this many seconds after close_ts
+}
+
+/// Supported price feed account layouts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OracleFormat {
+    /// Pyth legacy price account
+    PythLegacy,
 }
 
 /// User position structure for a specific market
@@ -33,16 +329,33 @@ pub struct Market {
 pub struct UserMarketPosition {
     pub owner: Pubkey,       // Position owner
     pub market_id: u64,
+    pub cost_basis: u64,     // Net collateral paid into the market
+    pub closed_paid: u64,    // `paid` of bins dropped once emptied
+    pub closed_received: u64, // `received` of bins dropped once emptied
     
     // Internal ledger
     pub bins: Vec<BinBal>,
 }
 
+impl UserMarketPosition {
+    /// Account size holding `bins` bin balances (positions are resized to fit)
+    pub fn space(bins: usize) -> usize {
+        8 + 32 + 8 + 8 + 8 + 8 + 4 + bins * BinBal::SIZE
+    }
+}
+
 /// BinBal structure (stored within user position)
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub struct BinBal {
     pub index: u16, // Array index value (0-based index)
     pub amount: u64,
+    pub paid: u64,     // Cumulative collateral paid for this bin
+    pub received: u64, // Cumulative collateral received from this bin
+}
+
+impl BinBal {
+    /// Serialized size of one bin balance
+    pub const SIZE: usize = 2 + 8 + 8 + 8;
 }
 
 /// Event definitions
@@ -52,6 +365,15 @@ pub struct MarketCreated {
     pub tick_spacing: u32,
     pub min_tick: i64,
     pub max_tick: i64,
+    pub creator: Pubkey,
+    pub bond: u64,
+}
+
+#[event]
+pub struct MarketBinsAllocated {
+    pub market_id: u64,
+    pub allocated: u32,
+    pub bin_count: u32,
 }
 
 #[event]
@@ -59,6 +381,15 @@ pub struct TokensBought {
     pub market_id: u64,
     pub buyer: Pubkey,
     pub total_cost: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct TokensSold {
+    pub market_id: u64,
+    pub seller: Pubkey,
+    pub total_revenue: u64,
+    pub fee: u64,
 }
 
 #[event]
@@ -67,6 +398,125 @@ pub struct MarketClosed {
     pub winning_bin: u16,
 }
 
+#[event]
+pub struct ResolutionProposed {
+    pub market_id: u64,
+    pub proposer: Pubkey,
+    pub winning_bin: u16,
+    pub challenge_deadline: i64,
+}
+
+#[event]
+pub struct ResolutionOverridden {
+    pub market_id: u64,
+    pub authority: Pubkey,
+    pub old_winning_bin: u16,
+    pub new_winning_bin: u16,
+}
+
+#[event]
+pub struct OracleResolved {
+    pub market_id: u64,
+    pub price: i64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub tick: i64,
+    pub winning_bin: u16,
+}
+
+#[event]
+pub struct CloseTimeExtended {
+    pub market_id: u64,
+    pub old_close_ts: i64,
+    pub new_close_ts: i64,
+}
+
+#[event]
+pub struct NoWinner {
+    pub market_id: u64,
+    pub policy: NoWinnerPolicy,
+    pub pool: u64,
+}
+
+#[event]
+pub struct MarketVoided {
+    pub market_id: u64,
+    pub refund_deadline: i64,
+}
+
+#[event]
+pub struct PositionRefunded {
+    pub market_id: u64,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PositionClosed {
+    pub market_id: u64,
+    pub owner: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub account: Pubkey,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub account: Pubkey,
+}
+
+#[event]
+pub struct ProgramPauseSet {
+    pub paused: bool,
+    pub claims_open: bool,
+}
+
+#[event]
+pub struct OwnershipTransferProposed {
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+}
+
+#[event]
+pub struct OwnershipTransferCancelled {
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+}
+
+#[event]
+pub struct OwnershipTransferred {
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct FeesCollected {
+    pub market_id: u64,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub creator_amount: u64,
+}
+
+#[event]
+pub struct BondSettled {
+    pub market_id: u64,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub slashed: bool,
+}
+
+#[event]
+pub struct MarketFinalized {
+    pub market_id: u64,
+    pub creator: Pubkey,
+    pub swept: u64,
+}
+
 #[event]
 pub struct RewardClaimed {
     pub market_id: u64,
@@ -78,4 +528,6 @@ pub struct RewardClaimed {
 pub struct CollateralOut {
     pub to: Pubkey,
     pub amount: u64,
+    pub residual: u64,
+    pub unclaimed: u64,
 } 
\ No newline at end of file
@@ -0,0 +1,155 @@
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { setupTestEnvironment, testCloseTime, TestEnv } from "./setup";
+
+describe("Access Control", () => {
+  let env: TestEnv;
+
+  before(async () => {
+    env = await setupTestEnvironment();
+  });
+
+  beforeEach(async () => {
+    // Initialize market for each test
+    await env.resetMarket();
+  });
+
+  afterEach(async () => {
+    // Roles fall back to the owner for other tests
+    for (const role of [
+      { marketCreator: {} },
+      { resolver: {} },
+      { pauser: {} },
+      { treasurer: {} },
+    ]) {
+      await env.program.methods
+        .revokeRole(role)
+        .accounts({
+          owner: env.admin.publicKey,
+        })
+        .rpc();
+    }
+  });
+
+  async function grantRole(role, account) {
+    await env.program.methods
+      .grantRole(role, account)
+      .accounts({
+        owner: env.admin.publicKey,
+      })
+      .rpc();
+  }
+
+  async function createMarket(authority) {
+    await env.program.methods
+      .createMarket({
+        tickSpacing: 60,
+        minTick: new BN(-360),
+        maxTick: new BN(360),
+        closeTs: new BN(testCloseTime()),
+        noWinnerPolicy: { treasury: {} },
+        tradingCutoff: new BN(0),
+        seriesPrevious: null,
+        oracle: null,
+        disputeWindow: new BN(0),
+        tailBins: false,
+        claimWindow: new BN(0),
+      })
+      .accounts({
+        authority: authority.publicKey,
+        collateralMint: env.collateralMint,
+        creatorTokenAccount: null,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  it("Granted market creator should create markets but not move funds", async () => {
+    await grantRole({ marketCreator: {} }, env.user1.publicKey);
+
+    const state = await env.program.account.programState.fetch(
+      env.programState
+    );
+    expect(state.marketCreator.toString()).to.equal(
+      env.user1.publicKey.toString()
+    );
+
+    await createMarket(env.user1);
+
+    await env.closeMarket(env.marketId, 0);
+    try {
+      await env.program.methods
+        .withdrawCollateral(new BN(env.marketId))
+        .accounts({
+          authority: env.user1.publicKey,
+          treasuryTokenAccount: env.userTokenAccounts.admin,
+          vault: env.vault,
+          nextMarket: null,
+          nextVault: null,
+        })
+        .signers([env.user1])
+        .rpc();
+      expect.fail("Market creator should not withdraw collateral");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Only the owner or the treasurer can perform this action"
+      );
+    }
+  });
+
+  it("Revoked market creator should no longer create markets", async () => {
+    await grantRole({ marketCreator: {} }, env.user1.publicKey);
+    await env.program.methods
+      .revokeRole({ marketCreator: {} })
+      .accounts({
+        owner: env.admin.publicKey,
+      })
+      .rpc();
+
+    const state = await env.program.account.programState.fetch(
+      env.programState
+    );
+    expect(state.marketCreator.toString()).to.equal(
+      env.admin.publicKey.toString()
+    );
+
+    try {
+      await createMarket(env.user1);
+      expect.fail("Revoked market creator should not create markets");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Only the owner or the market creator can perform this action"
+      );
+    }
+  });
+
+  it("Granted pauser should deactivate markets", async () => {
+    await grantRole({ pauser: {} }, env.user2.publicKey);
+
+    await env.program.methods
+      .activateMarket(new BN(env.marketId), false)
+      .accounts({
+        authority: env.user2.publicKey,
+      })
+      .signers([env.user2])
+      .rpc();
+
+    const marketInfo = await env.program.account.market.fetch(env.market);
+    expect(marketInfo.active).to.be.false;
+  });
+
+  it("Only the owner can grant roles", async () => {
+    try {
+      await env.program.methods
+        .grantRole({ treasurer: {} }, env.user1.publicKey)
+        .accounts({
+          owner: env.user1.publicKey,
+        })
+        .signers([env.user1])
+        .rpc();
+      expect.fail("Granting a role by another user should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Owner only function");
+    }
+  });
+});
@@ -31,6 +31,7 @@ describe("Bin Range Query", () => {
   async function getBinRangeData(startBin: number, endBin: number) {
     // Get market information
     const marketInfo = await env.program.account.market.fetch(env.market);
+    const marketBins = await env.getMarketBins(env.marketId);
 
     // Validate range
     if (startBin > endBin) {
@@ -61,7 +62,7 @@ describe("Bin Range Query", () => {
     for (let i = startBin; i <= endBin; i++) {
       // Check if it's within market range and calculate bin index
       const binIndex = i;
-      const binAmount = marketInfo.bins[binIndex] || new BN(0);
+      const binAmount = marketBins[binIndex] || new BN(0);
 
       // Add quantity for each bin
       amounts.push(binAmount);
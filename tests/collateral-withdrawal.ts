@@ -1,6 +1,6 @@
 import { expect } from "chai";
 import { BN } from "bn.js";
-import { setupTestEnvironment, TestEnv } from "./setup";
+import { setupTestEnvironment, TestEnv, waitUntil } from "./setup";
 
 describe("Collateral Withdrawal", () => {
   let env: TestEnv;
@@ -44,9 +44,11 @@ describe("Collateral Withdrawal", () => {
       await env.program.methods
         .withdrawCollateral(new BN(env.marketId))
         .accounts({
-          owner: env.admin.publicKey,
-          ownerTokenAccount: env.userTokenAccounts.admin,
+          authority: env.admin.publicKey,
+          treasuryTokenAccount: env.userTokenAccounts.admin,
           vault: env.vault,
+          nextMarket: null,
+          nextVault: null,
         })
         .rpc();
 
@@ -58,38 +60,44 @@ describe("Collateral Withdrawal", () => {
 
   it("Non-admin users should not be able to withdraw collateral", async () => {
     // Close market
-    await env.closeMarketsSequentially(env.marketId, 0);
+    await env.closeMarket(env.marketId, 0);
 
     // Regular user attempts to withdraw collateral
     try {
       await env.program.methods
         .withdrawCollateral(new BN(env.marketId))
         .accounts({
-          owner: env.user1.publicKey,
-          ownerTokenAccount: env.userTokenAccounts.user1,
+          authority: env.user1.publicKey,
+          treasuryTokenAccount: env.userTokenAccounts.user1,
           vault: env.vault,
+          nextMarket: null,
+          nextVault: null,
         })
         .signers([env.user1])
         .rpc();
 
       expect.fail("Collateral withdrawal by non-admin user should fail");
     } catch (e) {
-      expect(e.toString()).to.include("Owner only function");
+      expect(e.toString()).to.include(
+        "Only the owner or the treasurer can perform this action"
+      );
     }
   });
 
   it("Withdrawal attempt should fail when there's no collateral", async () => {
     // Close market
-    await env.closeMarketsSequentially(env.marketId, 0);
+    await env.closeMarket(env.marketId, 0);
 
     // Attempt to withdraw from market with no token purchases
     try {
       await env.program.methods
         .withdrawCollateral(new BN(env.marketId))
         .accounts({
-          owner: env.admin.publicKey,
-          ownerTokenAccount: env.userTokenAccounts.admin,
+          authority: env.admin.publicKey,
+          treasuryTokenAccount: env.userTokenAccounts.admin,
           vault: env.vault,
+          nextMarket: null,
+          nextVault: null,
         })
         .rpc();
 
@@ -99,6 +107,19 @@ describe("Collateral Withdrawal", () => {
     }
   });
 
+  async function withdraw(marketId = env.marketId, vault = env.vault) {
+    await env.program.methods
+      .withdrawCollateral(new BN(marketId))
+      .accounts({
+        authority: env.admin.publicKey,
+        treasuryTokenAccount: env.userTokenAccounts.admin,
+        vault,
+        nextMarket: null,
+        nextVault: null,
+      })
+      .rpc();
+  }
+
   it("Should be able to withdraw collateral from closed market", async () => {
     // First have user buy tokens to add collateral
     const binIndices = [0, 1];
@@ -121,21 +142,105 @@ describe("Collateral Withdrawal", () => {
     const collateralAmount = marketInfoBefore.collateralBalance;
     expect(collateralAmount.toString()).to.not.equal("0");
 
-    // Close markets sequentially
-    await env.closeMarketsSequentially(env.marketId, 0);
+    // Nobody holds bin 2, so the whole pool goes to the treasury
+    await env.closeMarket(env.marketId, 2);
 
     // Admin withdraws collateral
+    await withdraw();
+
+    // Verify market info
+    const marketInfoAfter = await env.program.account.market.fetch(env.market);
+    expect(marketInfoAfter.collateralBalance.toString()).to.equal("0"); // Collateral = 0
+  });
+
+  it("Unclaimed winnings cannot be withdrawn before the claim deadline", async () => {
     await env.program.methods
-      .withdrawCollateral(new BN(env.marketId))
+      .buyTokens(
+        new BN(env.marketId),
+        [0],
+        [new BN(100_000_000_000)],
+        new BN(1_000_000_000_000)
+      )
       .accounts({
-        owner: env.admin.publicKey,
-        ownerTokenAccount: env.userTokenAccounts.admin,
+        user: env.user1.publicKey,
+        userTokenAccount: env.userTokenAccounts.user1,
         vault: env.vault,
       })
+      .signers([env.user1])
       .rpc();
 
-    // Verify market info
-    const marketInfoAfter = await env.program.account.market.fetch(env.market);
-    expect(marketInfoAfter.collateralBalance.toString()).to.equal("0"); // Collateral = 0
+    await env.closeMarket(env.marketId, 0);
+
+    try {
+      await withdraw();
+      expect.fail("Withdrawing unclaimed winnings should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Winnings are still unclaimed");
+    }
+
+    const marketInfo = await env.program.account.market.fetch(env.market);
+    expect(marketInfo.remainingPayout.toString()).to.equal(
+      marketInfo.collateralBalance.toString()
+    );
+  });
+
+  it("Unclaimed winnings should be swept after the claim deadline", async () => {
+    const { market, marketId, vault } = await env.createNewMarket({
+      claimWindow: 2,
+    });
+
+    await env.program.methods
+      .buyTokens(
+        new BN(marketId),
+        [0],
+        [new BN(100_000_000_000)],
+        new BN(1_000_000_000_000)
+      )
+      .accounts({
+        user: env.user1.publicKey,
+        userTokenAccount: env.userTokenAccounts.user1,
+        vault,
+      })
+      .signers([env.user1])
+      .rpc();
+
+    await env.closeMarket(marketId, 0);
+    const marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.claimDeadline.toNumber()).to.be.greaterThan(0);
+
+    await waitUntil(marketInfo.claimDeadline.toNumber());
+
+    const before = await env.provider.connection.getTokenAccountBalance(
+      env.userTokenAccounts.admin
+    );
+    await withdraw(marketId, vault);
+    const after = await env.provider.connection.getTokenAccountBalance(
+      env.userTokenAccounts.admin
+    );
+
+    expect(
+      new BN(after.value.amount).sub(new BN(before.value.amount)).toString()
+    ).to.equal(marketInfo.collateralBalance.toString());
+
+    const marketAfter = await env.program.account.market.fetch(market);
+    expect(marketAfter.collateralBalance.toString()).to.equal("0");
+    expect(marketAfter.remainingPayout.toString()).to.equal("0");
+
+    // Winner can no longer claim
+    try {
+      await env.program.methods
+        .claimReward()
+        .accounts({
+          user: env.user1.publicKey,
+          userPosition: await env.getUserPosition(env.user1, marketId),
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault,
+        })
+        .signers([env.user1])
+        .rpc();
+      expect.fail("Claim after the deadline should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Claim deadline has passed");
+    }
   });
 });
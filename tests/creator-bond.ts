@@ -0,0 +1,290 @@
+import { expect } from "chai";
+import { BN } from "bn.js";
+import * as anchor from "@coral-xyz/anchor";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { setupTestEnvironment, testCloseTime, TestEnv } from "./setup";
+
+const BOND = new BN(1_000_000_000);
+const CREATOR_FEE_BPS = 2_000; // 20% of collected fees
+
+describe("Creator Bond", () => {
+  let env: TestEnv;
+
+  before(async () => {
+    env = await setupTestEnvironment();
+    await setCreatorConfig(BOND, CREATOR_FEE_BPS);
+  });
+
+  after(async () => {
+    // Restore role-only market creation for other tests
+    await setCreatorConfig(new BN(0), 0);
+  });
+
+  async function setCreatorConfig(
+    bond: BN,
+    creatorFeeBps: number,
+    minDisputeWindow = 0,
+    minClaimWindow = 0
+  ) {
+    await env.program.methods
+      .setCreatorConfig(
+        bond,
+        creatorFeeBps,
+        new BN(minDisputeWindow),
+        new BN(minClaimWindow)
+      )
+      .accounts({
+        owner: env.admin.publicKey,
+      })
+      .rpc();
+  }
+
+  // Create a market as user1 without the market creator role
+  async function createBondedMarket(
+    creatorTokenAccount = env.userTokenAccounts.user1,
+    overrides = {}
+  ) {
+    const state = await env.program.account.programState.fetch(
+      env.programState
+    );
+    const marketId = state.marketCount.toNumber();
+    const idBytes = new BN(marketId).toArrayLike(Buffer, "le", 8);
+    const [market] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), idBytes],
+      env.program.programId
+    );
+    const [vaultAuthority] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      env.program.programId
+    );
+    const vault = getAssociatedTokenAddressSync(
+      env.collateralMint,
+      vaultAuthority,
+      true
+    );
+
+    await env.program.methods
+      .createMarket({
+        tickSpacing: 60,
+        minTick: new BN(-360),
+        maxTick: new BN(360),
+        closeTs: new BN(testCloseTime()),
+        noWinnerPolicy: { treasury: {} },
+        tradingCutoff: new BN(0),
+        seriesPrevious: null,
+        oracle: null,
+        disputeWindow: new BN(0),
+        tailBins: false,
+        claimWindow: new BN(0),
+        ...overrides,
+      })
+      .accounts({
+        authority: env.user1.publicKey,
+        collateralMint: env.collateralMint,
+        creatorTokenAccount: creatorTokenAccount,
+      })
+      .signers([env.user1])
+      .rpc();
+
+    return { marketId, market, vault };
+  }
+
+  async function settleBond(marketId: number, vault) {
+    await env.program.methods
+      .settleBond(new BN(marketId))
+      .accounts({
+        payer: env.admin.publicKey,
+        creatorTokenAccount: env.userTokenAccounts.user1,
+        treasuryTokenAccount: env.userTokenAccounts.admin,
+        vault: vault,
+      })
+      .rpc();
+  }
+
+  it("Anyone should create a market by posting the bond", async () => {
+    const before = await env.balance(env.userTokenAccounts.user1);
+    const { market, vault } = await createBondedMarket();
+
+    const marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.creator.toString()).to.equal(
+      env.user1.publicKey.toString()
+    );
+    expect(marketInfo.bond.toString()).to.equal(BOND.toString());
+    expect(marketInfo.creatorFeeBps).to.equal(CREATOR_FEE_BPS);
+    // Bond sits in the vault but is not collateral
+    expect(marketInfo.collateralBalance.toString()).to.equal("0");
+    expect((await env.balance(vault)).toString()).to.equal(BOND.toString());
+    expect(
+      before.sub(await env.balance(env.userTokenAccounts.user1)).toString()
+    ).to.equal(BOND.toString());
+  });
+
+  it("Creating without the role requires a creator token account", async () => {
+    try {
+      await createBondedMarket(null);
+      expect.fail("Creating without posting the bond should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Creator token account is required");
+    }
+  });
+
+  it("Bonded creators cannot set an oracle or shorten the windows", async () => {
+    try {
+      await createBondedMarket(env.userTokenAccounts.user1, {
+        oracle: {
+          feed: anchor.web3.Keypair.generate().publicKey,
+          feedOwner: anchor.web3.SystemProgram.programId,
+          format: { pythLegacy: {} },
+          tickExpo: 0,
+          maxConfBps: 100,
+          maxPublishDelay: new BN(60),
+        },
+      });
+      expect.fail("Bonded market with an oracle should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Only the market creator role can configure an oracle"
+      );
+    }
+
+    await setCreatorConfig(BOND, CREATOR_FEE_BPS, 3600, 86400);
+    try {
+      try {
+        await createBondedMarket(env.userTokenAccounts.user1, {
+          disputeWindow: new BN(60),
+          claimWindow: new BN(86400),
+        });
+        expect.fail("Dispute window below the minimum should fail");
+      } catch (e) {
+        expect(e.toString()).to.include(
+          "Dispute window is below the minimum for bonded markets"
+        );
+      }
+
+      try {
+        await createBondedMarket(env.userTokenAccounts.user1, {
+          disputeWindow: new BN(3600),
+          claimWindow: new BN(60),
+        });
+        expect.fail("Claim window below the minimum should fail");
+      } catch (e) {
+        expect(e.toString()).to.include(
+          "Claim window is below the minimum for bonded markets"
+        );
+      }
+
+      // No claim deadline is always allowed
+      const { market } = await createBondedMarket(
+        env.userTokenAccounts.user1,
+        { disputeWindow: new BN(3600), claimWindow: new BN(0) }
+      );
+      const marketInfo = await env.program.account.market.fetch(market);
+      expect(marketInfo.disputeWindow.toNumber()).to.equal(3600);
+    } finally {
+      await setCreatorConfig(BOND, CREATOR_FEE_BPS);
+    }
+  });
+
+  it("Bond should be returned after a clean resolution", async () => {
+    const { marketId, vault } = await createBondedMarket();
+    await env.closeMarket(marketId, 0);
+
+    const before = await env.balance(env.userTokenAccounts.user1);
+    await settleBond(marketId, vault);
+    const returned = (await env.balance(env.userTokenAccounts.user1)).sub(before);
+
+    expect(returned.toString()).to.equal(BOND.toString());
+  });
+
+  it("Bond should be slashed when the market is voided", async () => {
+    const { marketId, vault } = await createBondedMarket();
+    await env.program.methods
+      .voidMarket(
+        new BN(marketId),
+        new BN(Math.floor(Date.now() / 1000) + 3600)
+      )
+      .accounts({
+        authority: env.admin.publicKey,
+      })
+      .rpc();
+
+    const creatorBefore = await env.balance(env.userTokenAccounts.user1);
+    const treasuryBefore = await env.balance(env.userTokenAccounts.admin);
+    await settleBond(marketId, vault);
+
+    expect((await env.balance(env.userTokenAccounts.user1)).toString()).to.equal(
+      creatorBefore.toString()
+    );
+    expect(
+      (await env.balance(env.userTokenAccounts.admin)).sub(treasuryBefore).toString()
+    ).to.equal(BOND.toString());
+  });
+
+  it("Market cannot be finalized before its bond is settled", async () => {
+    const { marketId, vault } = await createBondedMarket();
+    await env.closeMarket(marketId, 0);
+
+    try {
+      await env.program.methods
+        .finalizeMarket(new BN(marketId))
+        .accounts({
+          authority: env.user1.publicKey,
+          treasuryTokenAccount: env.userTokenAccounts.admin,
+          vault: vault,
+        })
+        .signers([env.user1])
+        .rpc();
+      expect.fail("Finalizing with an unsettled bond should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Creator bond must be settled before the market is finalized"
+      );
+    }
+  });
+
+  it("Creator should receive its share of collected fees", async () => {
+    const { marketId, market, vault } = await createBondedMarket();
+    await env.program.methods
+      .setMarketFee(new BN(marketId), 100)
+      .accounts({
+        owner: env.admin.publicKey,
+      })
+      .rpc();
+
+    await env.program.methods
+      .buyTokens(
+        new BN(marketId),
+        [0],
+        [new BN(100_000_000_000)],
+        new BN(1_000_000_000_000)
+      )
+      .accounts({
+        user: env.user2.publicKey,
+        userTokenAccount: env.userTokenAccounts.user2,
+        vault: vault,
+      })
+      .signers([env.user2])
+      .rpc();
+    const fees = (await env.program.account.market.fetch(market)).feesAccrued;
+
+    const creatorBefore = await env.balance(env.userTokenAccounts.user1);
+    const treasuryBefore = await env.balance(env.userTokenAccounts.admin);
+    await env.program.methods
+      .collectFees(new BN(marketId))
+      .accounts({
+        authority: env.admin.publicKey,
+        treasuryTokenAccount: env.userTokenAccounts.admin,
+        vault: vault,
+        creatorTokenAccount: env.userTokenAccounts.user1,
+      })
+      .rpc();
+
+    const creatorShare = fees.muln(CREATOR_FEE_BPS).divn(10_000);
+    expect(
+      (await env.balance(env.userTokenAccounts.user1)).sub(creatorBefore).toString()
+    ).to.equal(creatorShare.toString());
+    expect(
+      (await env.balance(env.userTokenAccounts.admin)).sub(treasuryBefore).toString()
+    ).to.equal(fees.sub(creatorShare).toString());
+  });
+});
@@ -0,0 +1,190 @@
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { setupTestEnvironment, TestEnv, waitUntil } from "./setup";
+
+describe("Global Pause", () => {
+  let env: TestEnv;
+
+  before(async () => {
+    env = await setupTestEnvironment();
+  });
+
+  beforeEach(async () => {
+    // Initialize market for each test
+    await env.resetMarket();
+  });
+
+  afterEach(async () => {
+    // Resume for other tests
+    await setPaused(false, false);
+  });
+
+  async function setPaused(paused: boolean, claimsOpen: boolean) {
+    await env.program.methods
+      .setPaused(paused, claimsOpen)
+      .accounts({
+        authority: env.admin.publicKey,
+      })
+      .rpc();
+  }
+
+  async function claim() {
+    await env.program.methods
+      .claimReward()
+      .accounts({
+        user: env.user1.publicKey,
+        userPosition: await env.getUserPosition(env.user1, env.marketId),
+        userTokenAccount: env.userTokenAccounts.user1,
+        vault: env.vault,
+      })
+      .signers([env.user1])
+      .rpc();
+  }
+
+  it("Paused program should reject trades in every market", async () => {
+    const other = await env.createNewMarket();
+    await setPaused(true, false);
+
+    try {
+      await env.buy(
+        env.user1,
+        env.userTokenAccounts.user1,
+        0,
+        new BN(100_000_000_000)
+      );
+      expect.fail("Buying while paused should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Program is paused");
+    }
+
+    try {
+      await env.program.methods
+        .buyTokens(
+          new BN(other.marketId),
+          [0],
+          [new BN(100_000_000_000)],
+          new BN(1_000_000_000_000)
+        )
+        .accounts({
+          user: env.user1.publicKey,
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault: other.vault,
+        })
+        .signers([env.user1])
+        .rpc();
+      expect.fail("Buying in another market while paused should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Program is paused");
+    }
+
+    // Trading resumes after unpausing
+    await setPaused(false, false);
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000)
+    );
+  });
+
+  it("Full pause should also halt claims", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000)
+    );
+    await env.closeMarket(env.marketId, 0);
+    await setPaused(true, false);
+
+    try {
+      await claim();
+      expect.fail("Claiming during a full pause should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Claims are paused");
+    }
+  });
+
+  it("Trading-only pause should keep claims open", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000)
+    );
+    await env.closeMarket(env.marketId, 0);
+    await setPaused(true, true);
+
+    await claim();
+
+    const position = await env.program.account.userMarketPosition.fetch(
+      await env.getUserPosition(env.user1, env.marketId)
+    );
+    expect(position.bins.length).to.equal(0);
+  });
+
+  it("Unclaimed payouts cannot be swept while claims are paused", async () => {
+    const { market, marketId, vault } = await env.createNewMarket({
+      claimWindow: 2,
+    });
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000),
+      marketId,
+      vault
+    );
+    await env.closeMarket(marketId, 0);
+    const marketInfo = await env.program.account.market.fetch(market);
+    await waitUntil(marketInfo.claimDeadline.toNumber());
+    await setPaused(true, false);
+
+    try {
+      await env.program.methods
+        .withdrawCollateral(new BN(marketId))
+        .accounts({
+          authority: env.admin.publicKey,
+          treasuryTokenAccount: env.userTokenAccounts.admin,
+          vault,
+          nextMarket: null,
+          nextVault: null,
+        })
+        .rpc();
+      expect.fail("Sweeping unclaimed payouts during a pause should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Claims are paused");
+    }
+
+    try {
+      await env.program.methods
+        .finalizeMarket(new BN(marketId))
+        .accounts({
+          authority: env.admin.publicKey,
+          treasuryTokenAccount: env.userTokenAccounts.admin,
+          vault,
+        })
+        .rpc();
+      expect.fail("Finalizing with unclaimed payouts during a pause should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Claims are paused");
+    }
+  });
+
+  it("Only the owner or the pauser can pause the program", async () => {
+    try {
+      await env.program.methods
+        .setPaused(true, false)
+        .accounts({
+          authority: env.user2.publicKey,
+        })
+        .signers([env.user2])
+        .rpc();
+      expect.fail("Pausing by another user should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Only the owner or the pauser can perform this action"
+      );
+    }
+  });
+});
@@ -1,6 +1,6 @@
 import { expect } from "chai";
 import { BN } from "bn.js";
-import { setupTestEnvironment, TestEnv } from "./setup";
+import { setupTestEnvironment, waitUntil, TestEnv } from "./setup";
 
 describe("Market Close", () => {
   let env: TestEnv;
@@ -9,14 +9,6 @@ describe("Market Close", () => {
   before(async () => {
     env = await setupTestEnvironment();
 
-    // Check last closed market ID from program state
-    const programState = await env.program.account.programState.fetch(
-      env.programState
-    );
-    const lastClosedMarketId = programState.lastClosedMarket
-      ? programState.lastClosedMarket.toNumber()
-      : -1;
-
     // Check current market status
     let needNewMarket = false;
 
@@ -140,8 +132,8 @@ describe("Market Close", () => {
       await setupTestBets();
     }
 
-    // Close markets sequentially (up to current market ID)
-    await env.closeMarketsSequentially(env.marketId, 0);
+    // Close market
+    await env.closeMarket(env.marketId, 0);
 
     // Check market information
     const updatedMarketInfo = await env.program.account.market.fetch(
@@ -184,15 +176,14 @@ describe("Market Close", () => {
     // Try to close market with out of range winning bin
     const outOfRangeIndex = Math.floor(env.maxTick / env.tickSpacing) + 10; // Ensure out of range
 
-    // Close all markets up to the previous one
-    await env.closeMarketsSequentially(env.marketId - 1, 0);
+    await waitUntil(newMarket.closeTs);
 
     try {
       // Try to close current market directly (with invalid value)
       await env.program.methods
-        .closeMarket(new BN(env.marketId), outOfRangeIndex)
+        .proposeResolution(new BN(env.marketId), outOfRangeIndex)
         .accounts({
-          owner: env.admin.publicKey,
+          authority: env.admin.publicKey,
         })
         .rpc();
 
@@ -202,6 +193,35 @@ describe("Market Close", () => {
     }
   });
 
+  it("Market should be resolvable by tick value", async () => {
+    const { market, marketId, closeTs } = await env.createNewMarket({
+      disputeWindow: 3600,
+    });
+    await waitUntil(closeTs);
+
+    // Tick 120 is bin 8 (min_tick -360, spacing 60)
+    await env.program.methods
+      .proposeResolutionAtTick(new BN(marketId), new BN(120))
+      .accounts({
+        authority: env.admin.publicKey,
+      })
+      .rpc();
+
+    let marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.proposedBin).to.equal(8);
+
+    // Off-grid ticks resolve to the bin containing them, as with oracle prices
+    await env.program.methods
+      .overrideResolutionAtTick(new BN(marketId), new BN(90))
+      .accounts({
+        authority: env.admin.publicKey,
+      })
+      .rpc();
+
+    marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.proposedBin).to.equal(7);
+  });
+
   it("Cannot close already closed market", async () => {
     // Create new market
     const newMarket = await env.createNewMarket();
@@ -209,15 +229,15 @@ describe("Market Close", () => {
     env.marketId = newMarket.marketId;
     await setupTestBets();
 
-    // Close markets sequentially
-    await env.closeMarketsSequentially(env.marketId, 0);
+    // Close market
+    await env.closeMarket(env.marketId, 0);
 
     // Try to close again
     try {
       await env.program.methods
-        .closeMarket(new BN(env.marketId), 1) // Try to close with different winning bin
+        .proposeResolution(new BN(env.marketId), 1) // Try to close with different winning bin
         .accounts({
-          owner: env.admin.publicKey,
+          authority: env.admin.publicKey,
         })
         .rpc();
 
@@ -227,7 +247,7 @@ describe("Market Close", () => {
     }
   });
 
-  it("Last closed market ID should be tracked correctly", async () => {
+  it("Closed market count should be tracked correctly", async () => {
     // Create new market
     const newMarket = await env.createNewMarket();
     env.market = newMarket.market;
@@ -238,31 +258,20 @@ describe("Market Close", () => {
     const initialState = await env.program.account.programState.fetch(
       env.programState
     );
-    const initialLastClosed = initialState.lastClosedMarket
-      ? initialState.lastClosedMarket.toNumber()
-      : -1;
 
-    // Close markets sequentially
-    await env.closeMarketsSequentially(env.marketId, 0);
+    // Close market
+    await env.closeMarket(env.marketId, 0);
 
     // Updated value check
     const updatedState = await env.program.account.programState.fetch(
       env.programState
     );
-    expect(updatedState.lastClosedMarket).to.not.be.null;
-    expect(updatedState.lastClosedMarket.toString()).to.equal(
-      env.marketId.toString()
+    expect(updatedState.closedMarketCount.toNumber()).to.equal(
+      initialState.closedMarketCount.toNumber() + 1
     );
-
-    // Check if updated value is greater than initial value
-    if (initialLastClosed >= 0) {
-      expect(updatedState.lastClosedMarket.toNumber()).to.be.greaterThan(
-        initialLastClosed
-      );
-    }
   });
 
-  it("Multiple markets should be able to close sequentially", async () => {
+  it("Multiple markets should be able to close in any order", async () => {
     // Existing market creation or new market creation
     const marketInfo = await env.program.account.market.fetch(env.market);
     if (marketInfo.closed) {
@@ -276,27 +285,230 @@ describe("Market Close", () => {
     const { market: newMarket, marketId: newMarketId } =
       await env.createNewMarket();
 
+    // Close second market first
+    await env.closeMarket(newMarketId, 1);
+
+    // Check second market closed status
+    const secondMarketInfo = await env.program.account.market.fetch(newMarket);
+    expect(secondMarketInfo.closed).to.be.true;
+    expect(secondMarketInfo.winningBin.toString()).to.equal("1");
+
+    // First market is unaffected
+    const firstMarketBefore = await env.program.account.market.fetch(
+      env.market
+    );
+    expect(firstMarketBefore.closed).to.be.false;
+
     // Close first market
-    await env.closeMarketsSequentially(env.marketId, 0);
+    await env.closeMarket(env.marketId, 0);
 
     // Check first market closed status
     const firstMarketInfo = await env.program.account.market.fetch(env.market);
     expect(firstMarketInfo.closed).to.be.true;
+  });
 
-    // Close second market
-    await env.closeMarketsSequentially(newMarketId, 1);
+  it("Markets in a sequential series should close after their predecessor", async () => {
+    const first = await env.createNewMarket();
+    const second = await env.createNewMarket({
+      seriesPrevious: first.marketId,
+    });
+    await waitUntil(second.closeTs);
+
+    // Proposing is allowed, but finalization waits for the predecessor
+    await env.program.methods
+      .proposeResolution(new BN(second.marketId), 0)
+      .accounts({
+        authority: env.admin.publicKey,
+      })
+      .rpc();
 
-    // Check second market closed status
-    const secondMarketInfo = await env.program.account.market.fetch(newMarket);
-    expect(secondMarketInfo.closed).to.be.true;
-    expect(secondMarketInfo.winningBin.toString()).to.equal("1");
+    try {
+      await env.program.methods
+        .finalizeResolution(new BN(second.marketId))
+        .accounts({
+          finalizer: env.admin.publicKey,
+          seriesPrevious: first.market,
+        })
+        .rpc();
 
-    // Check program state last_closed_market
-    const programState = await env.program.account.programState.fetch(
-      env.programState
-    );
-    expect(programState.lastClosedMarket.toString()).to.equal(
-      newMarketId.toString()
-    );
+      expect.fail("Series market should not close before its predecessor");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Previous market in the series must be resolved first"
+      );
+    }
+
+    await env.closeMarket(first.marketId, 0);
+
+    // Predecessor account is required
+    try {
+      await env.program.methods
+        .finalizeResolution(new BN(second.marketId))
+        .accounts({
+          finalizer: env.admin.publicKey,
+          seriesPrevious: null,
+        })
+        .rpc();
+
+      expect.fail("Series market should require its predecessor account");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Previous market in the series must be resolved first"
+      );
+    }
+
+    await env.program.methods
+      .finalizeResolution(new BN(second.marketId))
+      .accounts({
+        finalizer: env.admin.publicKey,
+        seriesPrevious: first.market,
+      })
+      .rpc();
+
+    const secondInfo = await env.program.account.market.fetch(second.market);
+    expect(secondInfo.closed).to.be.true;
+    expect(secondInfo.seriesPrevious.toNumber()).to.equal(first.marketId);
+  });
+
+  it("Closing before close_ts should fail", async () => {
+    const closeTime = Math.floor(Date.now() / 1000) + 3600;
+    const { marketId } = await env.createNewMarket({ closeTime });
+
+    try {
+      await env.program.methods
+        .proposeResolution(new BN(marketId), 0)
+        .accounts({
+          authority: env.admin.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Market close before close_ts should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Market cannot be resolved before its close time"
+      );
+    }
+  });
+
+  it("Trading after close_ts should fail", async () => {
+    const { marketId, vault, closeTs } = await env.createNewMarket();
+    await waitUntil(closeTs);
+
+    try {
+      await env.program.methods
+        .buyTokens(
+          new BN(marketId),
+          [0],
+          [new BN(100_000_000_000)],
+          new BN(150_000_000_000)
+        )
+        .accounts({
+          user: env.user1.publicKey,
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault,
+        })
+        .signers([env.user1])
+        .rpc();
+
+      expect.fail("Purchase after close_ts should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Trading period has ended");
+    }
+  });
+
+  it("Trading should stop at the trading cutoff", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { marketId, vault } = await env.createNewMarket({
+      closeTime: now + 3600,
+      tradingCutoff: 3590,
+    });
+    await waitUntil(now + 10);
+
+    try {
+      await env.program.methods
+        .buyTokens(
+          new BN(marketId),
+          [0],
+          [new BN(100_000_000_000)],
+          new BN(150_000_000_000)
+        )
+        .accounts({
+          user: env.user1.publicKey,
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault,
+        })
+        .signers([env.user1])
+        .rpc();
+
+      expect.fail("Purchase after the trading cutoff should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Trading period has ended");
+    }
+  });
+
+  it("Admin should be able to extend close_ts", async () => {
+    const { market, marketId, closeTs } = await env.createNewMarket();
+    const newCloseTs = closeTs + 60;
+
+    await env.program.methods
+      .extendCloseTs(new BN(marketId), new BN(newCloseTs))
+      .accounts({
+        authority: env.admin.publicKey,
+      })
+      .rpc();
+
+    const marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.closeTs.toNumber()).to.equal(newCloseTs);
+
+    // Close time cannot move earlier
+    try {
+      await env.program.methods
+        .extendCloseTs(new BN(marketId), new BN(closeTs))
+        .accounts({
+          authority: env.admin.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Moving close_ts earlier should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "New close time must be later than the current close time"
+      );
+    }
+
+    // Regular users cannot extend
+    try {
+      await env.program.methods
+        .extendCloseTs(new BN(marketId), new BN(newCloseTs + 60))
+        .accounts({
+          authority: env.user1.publicKey,
+        })
+        .signers([env.user1])
+        .rpc();
+
+      expect.fail("Extension by non-admin user should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Only the owner or the market creator can perform this action"
+      );
+    }
+  });
+
+  it("Close time cannot be extended once trading has ended", async () => {
+    const { marketId, closeTs } = await env.createNewMarket();
+    await waitUntil(closeTs);
+
+    try {
+      await env.program.methods
+        .extendCloseTs(new BN(marketId), new BN(closeTs + 3600))
+        .accounts({
+          authority: env.admin.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Extending a market past its close time should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Trading period has ended");
+    }
   });
 });
@@ -1,7 +1,8 @@
 import * as anchor from "@coral-xyz/anchor";
 import { expect } from "chai";
 import { BN } from "bn.js";
-import { setupTestEnvironment, TestEnv } from "./setup";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { setupTestEnvironment, testCloseTime, TestEnv } from "./setup";
 
 describe("Market Creation", () => {
   let env: TestEnv;
@@ -11,8 +12,7 @@ describe("Market Creation", () => {
   });
 
   it("Market should be created with correct parameters", async () => {
-    // Set close time 2 weeks from now
-    const closeTime = Math.floor(Date.now() / 1000) + 14 * 24 * 60 * 60;
+    const closeTime = testCloseTime();
 
     // Get new market ID (current market count)
     const programState = await env.program.account.programState.fetch(
@@ -39,15 +39,23 @@ describe("Market Creation", () => {
 
     // Create new market
     await env.program.methods
-      .createMarket(
+      .createMarket({
         tickSpacing,
-        new BN(minTick),
-        new BN(maxTick),
-        new BN(closeTime)
-      )
+        minTick: new BN(minTick),
+        maxTick: new BN(maxTick),
+        closeTs: new BN(closeTime),
+        noWinnerPolicy: { treasury: {} },
+        tradingCutoff: new BN(0),
+        seriesPrevious: null,
+        oracle: null,
+        disputeWindow: new BN(0),
+        tailBins: false,
+        claimWindow: new BN(0),
+      })
       .accounts({
-        owner: env.admin.publicKey,
+        authority: env.admin.publicKey,
         collateralMint: env.collateralMint,
+        creatorTokenAccount: null,
       })
       .rpc();
 
@@ -65,23 +73,41 @@ describe("Market Creation", () => {
     expect(marketInfo.winningBin).to.be.null; // Not determined yet
     expect(marketInfo.openTs.toString()).to.not.equal("0"); // Open time should not be 0
     expect(marketInfo.closeTs.toString()).to.equal(closeTime.toString()); // Close time
+    expect(marketInfo.collateralMint.toString()).to.equal(
+      env.collateralMint.toString()
+    );
+    expect(marketInfo.vault.toString()).to.equal(
+      getAssociatedTokenAddressSync(
+        env.collateralMint,
+        vaultAuthority,
+        true
+      ).toString()
+    );
   });
 
   it("Market creation should fail with invalid tick parameters", async () => {
-    const closeTime = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const closeTime = testCloseTime();
 
     // When tickSpacing is 0 (must be positive)
     try {
       await env.program.methods
-        .createMarket(
-          0, // 0 is not allowed
-          new BN(-360),
-          new BN(360),
-          new BN(closeTime)
-        )
+        .createMarket({
+          tickSpacing: 0, // 0 is not allowed
+          minTick: new BN(-360),
+          maxTick: new BN(360),
+          closeTs: new BN(closeTime),
+          noWinnerPolicy: { treasury: {} },
+          tradingCutoff: new BN(0),
+          seriesPrevious: null,
+          oracle: null,
+          disputeWindow: new BN(0),
+          tailBins: false,
+          claimWindow: new BN(0),
+        })
         .accounts({
-          owner: env.admin.publicKey,
+          authority: env.admin.publicKey,
           collateralMint: env.collateralMint,
+          creatorTokenAccount: null,
         })
         .rpc();
 
@@ -93,15 +119,23 @@ describe("Market Creation", () => {
     // When minTick is not a multiple of tickSpacing
     try {
       await env.program.methods
-        .createMarket(
-          60,
-          new BN(-361), // Not a multiple of 60
-          new BN(360),
-          new BN(closeTime)
-        )
+        .createMarket({
+          tickSpacing: 60,
+          minTick: new BN(-361), // Not a multiple of 60
+          maxTick: new BN(360),
+          closeTs: new BN(closeTime),
+          noWinnerPolicy: { treasury: {} },
+          tradingCutoff: new BN(0),
+          seriesPrevious: null,
+          oracle: null,
+          disputeWindow: new BN(0),
+          tailBins: false,
+          claimWindow: new BN(0),
+        })
         .accounts({
-          owner: env.admin.publicKey,
+          authority: env.admin.publicKey,
           collateralMint: env.collateralMint,
+          creatorTokenAccount: null,
         })
         .rpc();
 
@@ -115,15 +149,23 @@ describe("Market Creation", () => {
     // When maxTick is not a multiple of tickSpacing
     try {
       await env.program.methods
-        .createMarket(
-          60,
-          new BN(-360),
-          new BN(361), // Not a multiple of 60
-          new BN(closeTime)
-        )
+        .createMarket({
+          tickSpacing: 60,
+          minTick: new BN(-360),
+          maxTick: new BN(361), // Not a multiple of 60
+          closeTs: new BN(closeTime),
+          noWinnerPolicy: { treasury: {} },
+          tradingCutoff: new BN(0),
+          seriesPrevious: null,
+          oracle: null,
+          disputeWindow: new BN(0),
+          tailBins: false,
+          claimWindow: new BN(0),
+        })
         .accounts({
-          owner: env.admin.publicKey,
+          authority: env.admin.publicKey,
           collateralMint: env.collateralMint,
+          creatorTokenAccount: null,
         })
         .rpc();
 
@@ -137,15 +179,23 @@ describe("Market Creation", () => {
     // When minTick >= maxTick
     try {
       await env.program.methods
-        .createMarket(
-          60,
-          new BN(360),
-          new BN(360), // Same as minTick
-          new BN(closeTime)
-        )
+        .createMarket({
+          tickSpacing: 60,
+          minTick: new BN(360),
+          maxTick: new BN(360), // Same as minTick
+          closeTs: new BN(closeTime),
+          noWinnerPolicy: { treasury: {} },
+          tradingCutoff: new BN(0),
+          seriesPrevious: null,
+          oracle: null,
+          disputeWindow: new BN(0),
+          tailBins: false,
+          claimWindow: new BN(0),
+        })
         .accounts({
-          owner: env.admin.publicKey,
+          authority: env.admin.publicKey,
           collateralMint: env.collateralMint,
+          creatorTokenAccount: null,
         })
         .rpc();
 
@@ -155,8 +205,96 @@ describe("Market Creation", () => {
     }
   });
 
+  it("Market creation should fail with invalid close time", async () => {
+    // When closeTime is in the past
+    try {
+      await env.program.methods
+        .createMarket({
+          tickSpacing: 60,
+          minTick: new BN(-360),
+          maxTick: new BN(360),
+          closeTs: new BN(Math.floor(Date.now() / 1000) - 60),
+          noWinnerPolicy: { treasury: {} },
+          tradingCutoff: new BN(0),
+          seriesPrevious: null,
+          oracle: null,
+          disputeWindow: new BN(0),
+          tailBins: false,
+          claimWindow: new BN(0),
+        })
+        .accounts({
+          authority: env.admin.publicKey,
+          collateralMint: env.collateralMint,
+          creatorTokenAccount: null,
+        })
+        .rpc();
+
+      expect.fail("Should fail when closeTime is in the past");
+    } catch (e) {
+      expect(e.toString()).to.include("Close time must be in the future");
+    }
+
+    // When the trading cutoff ends trading before now
+    try {
+      await env.program.methods
+        .createMarket({
+          tickSpacing: 60,
+          minTick: new BN(-360),
+          maxTick: new BN(360),
+          closeTs: new BN(testCloseTime()),
+          noWinnerPolicy: { treasury: {} },
+          tradingCutoff: new BN(3600),
+          seriesPrevious: null,
+          oracle: null,
+          disputeWindow: new BN(0),
+          tailBins: false,
+          claimWindow: new BN(0),
+        })
+        .accounts({
+          authority: env.admin.publicKey,
+          collateralMint: env.collateralMint,
+          creatorTokenAccount: null,
+        })
+        .rpc();
+
+      expect.fail("Should fail when the trading cutoff is already reached");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Trading cutoff must be non-negative and end trading after now"
+      );
+    }
+
+    // When the dispute window is negative
+    try {
+      await env.program.methods
+        .createMarket({
+          tickSpacing: 60,
+          minTick: new BN(-360),
+          maxTick: new BN(360),
+          closeTs: new BN(testCloseTime()),
+          noWinnerPolicy: { treasury: {} },
+          tradingCutoff: new BN(0),
+          seriesPrevious: null,
+          oracle: null,
+          disputeWindow: new BN(-1),
+          tailBins: false,
+          claimWindow: new BN(0),
+        })
+        .accounts({
+          authority: env.admin.publicKey,
+          collateralMint: env.collateralMint,
+          creatorTokenAccount: null,
+        })
+        .rpc();
+
+      expect.fail("Should fail when the dispute window is negative");
+    } catch (e) {
+      expect(e.toString()).to.include("Dispute window must be non-negative");
+    }
+  });
+
   it("Only admin should be able to create markets", async () => {
-    const closeTime = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const closeTime = testCloseTime();
     const programState = await env.program.account.programState.fetch(
       env.programState
     );
@@ -165,17 +303,32 @@ describe("Market Creation", () => {
     // Regular user attempts to create market
     try {
       await env.program.methods
-        .createMarket(60, new BN(-360), new BN(360), new BN(closeTime))
+        .createMarket({
+          tickSpacing: 60,
+          minTick: new BN(-360),
+          maxTick: new BN(360),
+          closeTs: new BN(closeTime),
+          noWinnerPolicy: { treasury: {} },
+          tradingCutoff: new BN(0),
+          seriesPrevious: null,
+          oracle: null,
+          disputeWindow: new BN(0),
+          tailBins: false,
+          claimWindow: new BN(0),
+        })
         .accounts({
-          owner: env.user1.publicKey, // Regular user, not admin
+          authority: env.user1.publicKey, // Regular user, not admin
           collateralMint: env.collateralMint,
+          creatorTokenAccount: null,
         })
         .signers([env.user1])
         .rpc();
 
       expect.fail("Non-admin user creating market should fail");
     } catch (e) {
-      expect(e.toString()).to.include("Owner only function");
+      expect(e.toString()).to.include(
+        "Only the owner or the market creator can perform this action"
+      );
     }
   });
 
@@ -192,19 +345,19 @@ describe("Market Creation", () => {
         tickSpacing: 60,
         minTick: -360,
         maxTick: 360,
-        closeTime: Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60,
+        closeTime: testCloseTime(),
       },
       {
         tickSpacing: 120,
         minTick: -720,
         maxTick: 720,
-        closeTime: Math.floor(Date.now() / 1000) + 14 * 24 * 60 * 60,
+        closeTime: testCloseTime(),
       },
       {
         tickSpacing: 180,
         minTick: -1080,
         maxTick: 1080,
-        closeTime: Math.floor(Date.now() / 1000) + 21 * 24 * 60 * 60,
+        closeTime: testCloseTime(),
       },
     ];
 
@@ -222,15 +375,23 @@ describe("Market Creation", () => {
       );
 
       await env.program.methods
-        .createMarket(
-          params.tickSpacing,
-          new BN(params.minTick),
-          new BN(params.maxTick),
-          new BN(params.closeTime)
-        )
+        .createMarket({
+          tickSpacing: params.tickSpacing,
+          minTick: new BN(params.minTick),
+          maxTick: new BN(params.maxTick),
+          closeTs: new BN(params.closeTime),
+          noWinnerPolicy: { treasury: {} },
+          tradingCutoff: new BN(0),
+          seriesPrevious: null,
+          oracle: null,
+          disputeWindow: new BN(0),
+          tailBins: false,
+          claimWindow: new BN(0),
+        })
         .accounts({
-          owner: env.admin.publicKey,
+          authority: env.admin.publicKey,
           collateralMint: env.collateralMint,
+          creatorTokenAccount: null,
         })
         .rpc();
 
@@ -260,7 +421,7 @@ describe("Market Creation", () => {
   });
 
   it("openTimestamp and closeTimestamp should be correctly stored", async () => {
-    const closeTime = Math.floor(Date.now() / 1000) + 14 * 24 * 60 * 60; // 2 weeks from now
+    const closeTime = testCloseTime();
 
     // Get new market ID
     const programState = await env.program.account.programState.fetch(
@@ -279,10 +440,23 @@ describe("Market Creation", () => {
 
     // Create market
     await env.program.methods
-      .createMarket(60, new BN(-540), new BN(540), new BN(closeTime))
+      .createMarket({
+        tickSpacing: 60,
+        minTick: new BN(-540),
+        maxTick: new BN(540),
+        closeTs: new BN(closeTime),
+        noWinnerPolicy: { treasury: {} },
+        tradingCutoff: new BN(0),
+        seriesPrevious: null,
+        oracle: null,
+        disputeWindow: new BN(0),
+        tailBins: false,
+        claimWindow: new BN(0),
+      })
       .accounts({
-        owner: env.admin.publicKey,
+        authority: env.admin.publicKey,
         collateralMint: env.collateralMint,
+        creatorTokenAccount: null,
       })
       .rpc();
 
@@ -294,4 +468,82 @@ describe("Market Creation", () => {
     expect(marketInfo.openTs.toNumber()).to.be.approximately(now, 10); // Open time should be close to current time (10 seconds tolerance)
     expect(marketInfo.closeTs.toNumber()).to.equal(closeTime); // Close time should be correctly set
   });
+
+  it("Large markets should be allocated incrementally before trading", async () => {
+    // 3,000 bins need three allocation steps of up to 1,024 bins
+    const { market, marketId, vault } = await env.createNewMarket({
+      tickSpacing: 1,
+      minTick: 0,
+      maxTick: 2999,
+    });
+
+    const marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.binCount).to.equal(3000);
+    expect((await env.getMarketBins(marketId)).length).to.equal(1024);
+
+    const buyLastBin = () =>
+      env.program.methods
+        .buyTokens(
+          new BN(marketId),
+          [2999],
+          [new BN(10_000_000_000)],
+          new BN(100_000_000_000)
+        )
+        .accounts({
+          user: env.user1.publicKey,
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault,
+        })
+        .signers([env.user1])
+        .rpc();
+
+    // Trading waits until every bin has storage
+    try {
+      await buyLastBin();
+      expect.fail("Buying before all bins are allocated should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Market bins are not fully allocated");
+    }
+
+    const allocate = () =>
+      env.program.methods
+        .allocateMarketBins(new BN(marketId))
+        .accounts({
+          authority: env.admin.publicKey,
+        })
+        .rpc();
+
+    await allocate();
+    expect((await env.getMarketBins(marketId)).length).to.equal(2048);
+    await allocate();
+    expect((await env.getMarketBins(marketId)).length).to.equal(3000);
+
+    try {
+      await allocate();
+      expect.fail("Allocating past the bin count should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("All market bins are already allocated");
+    }
+
+    await buyLastBin();
+    const bins = await env.getMarketBins(marketId);
+    expect(bins[2999].toString()).to.equal("10000000000");
+  });
+
+  it("Partly allocated markets should still resolve", async () => {
+    const { market, marketId } = await env.createNewMarket({
+      tickSpacing: 1,
+      minTick: 0,
+      maxTick: 2999,
+    });
+    expect((await env.getMarketBins(marketId)).length).to.equal(1024);
+
+    // The winning bin has no storage yet and is read as empty
+    await env.closeMarket(marketId, 2999);
+
+    const marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.closed).to.be.true;
+    expect(marketInfo.winningBin).to.equal(2999);
+    expect(marketInfo.winningSupply.toString()).to.equal("0");
+  });
 });
@@ -0,0 +1,130 @@
+import { expect } from "chai";
+import { BN } from "bn.js";
+import * as anchor from "@coral-xyz/anchor";
+import { setupTestEnvironment, TestEnv, waitUntil } from "./setup";
+
+describe("Market Finalize", () => {
+  let env: TestEnv;
+
+  before(async () => {
+    env = await setupTestEnvironment();
+  });
+
+  beforeEach(async () => {
+    // Initialize market for each test
+    await env.resetMarket();
+  });
+
+  async function finalizeMarket(marketId = env.marketId, vault = env.vault) {
+    await env.program.methods
+      .finalizeMarket(new BN(marketId))
+      .accounts({
+        authority: env.admin.publicKey,
+        treasuryTokenAccount: env.userTokenAccounts.admin,
+        vault: vault,
+      })
+      .rpc();
+  }
+
+  function marketBinsAddress(marketId: number) {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bins"), new BN(marketId).toArrayLike(Buffer, "le", 8)],
+      env.program.programId
+    )[0];
+  }
+
+  it("Market with unclaimed winnings cannot be finalized", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000)
+    );
+    await env.closeMarket(env.marketId, 0);
+
+    try {
+      await finalizeMarket();
+      expect.fail("Finalizing with unclaimed winnings should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Market still has unclaimed payouts or refunds"
+      );
+    }
+  });
+
+  it("Open market cannot be finalized", async () => {
+    try {
+      await finalizeMarket();
+      expect.fail("Finalizing an open market should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Market is not closed");
+    }
+  });
+
+  it("Settled market should close its accounts and return rent to the creator", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000)
+    );
+    await env.closeMarket(env.marketId, 0);
+
+    await env.program.methods
+      .claimReward()
+      .accounts({
+        user: env.user1.publicKey,
+        userPosition: await env.getUserPosition(env.user1, env.marketId),
+        userTokenAccount: env.userTokenAccounts.user1,
+        vault: env.vault,
+      })
+      .signers([env.user1])
+      .rpc();
+
+    const connection = env.provider.connection;
+    const marketBins = marketBinsAddress(env.marketId);
+    const rent =
+      (await connection.getBalance(env.market)) +
+      (await connection.getBalance(marketBins)) +
+      (await connection.getBalance(env.vault));
+    const before = await connection.getBalance(env.admin.publicKey);
+
+    await finalizeMarket();
+
+    expect(await connection.getAccountInfo(env.market)).to.be.null;
+    expect(await connection.getAccountInfo(marketBins)).to.be.null;
+    expect(await connection.getAccountInfo(env.vault)).to.be.null;
+
+    // Creator paid the transaction fee
+    const after = await connection.getBalance(env.admin.publicKey);
+    expect(after - before).to.be.greaterThan(rent - 10_000);
+  });
+
+  it("Finalized predecessor should count as resolved for its series", async () => {
+    const first = await env.createNewMarket();
+    const second = await env.createNewMarket({
+      seriesPrevious: first.marketId,
+    });
+
+    await env.closeMarket(first.marketId, 0);
+    await finalizeMarket(first.marketId, first.vault);
+
+    await waitUntil(second.closeTs);
+    await env.program.methods
+      .proposeResolution(new BN(second.marketId), 0)
+      .accounts({
+        authority: env.admin.publicKey,
+      })
+      .rpc();
+    await env.program.methods
+      .finalizeResolution(new BN(second.marketId))
+      .accounts({
+        finalizer: env.admin.publicKey,
+        seriesPrevious: first.market,
+      })
+      .rpc();
+
+    const secondInfo = await env.program.account.market.fetch(second.market);
+    expect(secondInfo.closed).to.be.true;
+  });
+});
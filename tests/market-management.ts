@@ -20,7 +20,7 @@ describe("Market Management", () => {
     await env.program.methods
       .activateMarket(new BN(env.marketId), false) // false = deactivate
       .accounts({
-        owner: env.admin.publicKey,
+        authority: env.admin.publicKey,
       })
       .rpc();
 
@@ -56,7 +56,7 @@ describe("Market Management", () => {
     await env.program.methods
       .activateMarket(new BN(env.marketId), true) // true = activate
       .accounts({
-        owner: env.admin.publicKey,
+        authority: env.admin.publicKey,
       })
       .rpc();
 
@@ -93,7 +93,7 @@ describe("Market Management", () => {
     await env.program.methods
       .activateMarket(new BN(env.marketId), false)
       .accounts({
-        owner: env.admin.publicKey,
+        authority: env.admin.publicKey,
       })
       .rpc();
 
@@ -105,7 +105,7 @@ describe("Market Management", () => {
     await env.program.methods
       .activateMarket(new BN(env.marketId), false)
       .accounts({
-        owner: env.admin.publicKey,
+        authority: env.admin.publicKey,
       })
       .rpc();
 
@@ -123,7 +123,7 @@ describe("Market Management", () => {
     await env.program.methods
       .activateMarket(new BN(env.marketId), true)
       .accounts({
-        owner: env.admin.publicKey,
+        authority: env.admin.publicKey,
       })
       .rpc();
 
@@ -134,7 +134,7 @@ describe("Market Management", () => {
 
   it("Closed market cannot be activated/deactivated", async () => {
     // Close markets sequentially
-    await env.closeMarketsSequentially(env.marketId, 0);
+    await env.closeMarket(env.marketId, 0);
 
     // Verify closing
     const marketInfo = await env.program.account.market.fetch(env.market);
@@ -145,7 +145,7 @@ describe("Market Management", () => {
       await env.program.methods
         .activateMarket(new BN(env.marketId), false)
         .accounts({
-          owner: env.admin.publicKey,
+          authority: env.admin.publicKey,
         })
         .rpc();
 
@@ -159,7 +159,7 @@ describe("Market Management", () => {
       await env.program.methods
         .activateMarket(new BN(env.marketId), true)
         .accounts({
-          owner: env.admin.publicKey,
+          authority: env.admin.publicKey,
         })
         .rpc();
 
@@ -175,14 +175,16 @@ describe("Market Management", () => {
       await env.program.methods
         .activateMarket(new BN(env.marketId), false)
         .accounts({
-          owner: env.user1.publicKey,
+          authority: env.user1.publicKey,
         })
         .signers([env.user1])
         .rpc();
 
       expect.fail("Non-admin user changing market state should fail");
     } catch (e) {
-      expect(e.toString()).to.include("Owner only function");
+      expect(e.toString()).to.include(
+        "Only the owner or the pauser can perform this action"
+      );
     }
   });
 });
@@ -0,0 +1,247 @@
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { setupTestEnvironment, TestEnv } from "./setup";
+
+describe("Market Void", () => {
+  let env: TestEnv;
+
+  before(async () => {
+    env = await setupTestEnvironment();
+  });
+
+  beforeEach(async () => {
+    // Initialize market for each test
+    await env.resetMarket();
+  });
+
+  async function voidMarket(refundDeadline: number, marketId = env.marketId) {
+    await env.program.methods
+      .voidMarket(new BN(marketId), new BN(refundDeadline))
+      .accounts({
+        authority: env.admin.publicKey,
+      })
+      .rpc();
+  }
+
+  async function refund(
+    user,
+    tokenAccount,
+    marketId = env.marketId,
+    vault = env.vault
+  ): Promise<BN> {
+    const before = await env.provider.connection.getTokenAccountBalance(
+      tokenAccount
+    );
+    await env.program.methods
+      .refundPosition()
+      .accounts({
+        user: user.publicKey,
+        userPosition: await env.getUserPosition(user, marketId),
+        userTokenAccount: tokenAccount,
+        vault,
+      })
+      .signers([user])
+      .rpc();
+    const after = await env.provider.connection.getTokenAccountBalance(
+      tokenAccount
+    );
+    return new BN(after.value.amount).sub(new BN(before.value.amount));
+  }
+
+  async function withdraw() {
+    await env.program.methods
+      .withdrawCollateral(new BN(env.marketId))
+      .accounts({
+        authority: env.admin.publicKey,
+        treasuryTokenAccount: env.userTokenAccounts.admin,
+        vault: env.vault,
+        nextMarket: null,
+        nextVault: null,
+      })
+      .rpc();
+  }
+
+  it("Voided market should refund each position at cost", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000)
+    );
+    await env.buy(
+      env.user2,
+      env.userTokenAccounts.user2,
+      1,
+      new BN(50_000_000_000)
+    );
+
+    const position1 = await env.program.account.userMarketPosition.fetch(
+      await env.getUserPosition(env.user1, env.marketId)
+    );
+    const position2 = await env.program.account.userMarketPosition.fetch(
+      await env.getUserPosition(env.user2, env.marketId)
+    );
+
+    await voidMarket(Math.floor(Date.now() / 1000) + 3600);
+
+    const marketInfo = await env.program.account.market.fetch(env.market);
+    expect(marketInfo.voided).to.be.true;
+    expect(marketInfo.closed).to.be.true;
+    expect(marketInfo.active).to.be.false;
+
+    // Owner cannot withdraw while refunds are outstanding
+    try {
+      await withdraw();
+      expect.fail("Withdrawal with outstanding refunds should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Refunds are still outstanding");
+    }
+
+    const refund1 = await refund(env.user1, env.userTokenAccounts.user1);
+    const refund2 = await refund(env.user2, env.userTokenAccounts.user2);
+
+    expect(refund1.toString()).to.equal(position1.costBasis.toString());
+    expect(refund2.toString()).to.equal(position2.costBasis.toString());
+
+    // Position is cleared
+    const position1After = await env.program.account.userMarketPosition.fetch(
+      await env.getUserPosition(env.user1, env.marketId)
+    );
+    expect(position1After.costBasis.toString()).to.equal("0");
+    expect(position1After.bins.length).to.equal(0);
+
+    const marketAfter = await env.program.account.market.fetch(env.market);
+    expect(marketAfter.remainingSupply.toString()).to.equal("0");
+    expect(marketAfter.collateralBalance.toString()).to.equal("0");
+  });
+
+  it("Refunds should not exceed cost when the vault holds extra collateral", async () => {
+    // Roll a no-winner pool into an open market before voiding it
+    const rolling = await env.createNewMarket({
+      noWinnerPolicy: { rollOver: {} },
+    });
+    const target = await env.createNewMarket({
+      closeTime: Math.floor(Date.now() / 1000) + 3600,
+    });
+    await env.buy(
+      env.user2,
+      env.userTokenAccounts.user2,
+      1,
+      new BN(50_000_000_000),
+      rolling.marketId,
+      rolling.vault
+    );
+    await env.closeMarket(rolling.marketId, 0);
+    await env.program.methods
+      .withdrawCollateral(new BN(rolling.marketId))
+      .accounts({
+        authority: env.admin.publicKey,
+        treasuryTokenAccount: env.userTokenAccounts.admin,
+        vault: rolling.vault,
+        nextMarket: target.market,
+        nextVault: target.vault,
+      })
+      .rpc();
+    const rolled = (await env.program.account.market.fetch(target.market))
+      .collateralBalance;
+
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000),
+      target.marketId,
+      target.vault
+    );
+    const position = await env.program.account.userMarketPosition.fetch(
+      await env.getUserPosition(env.user1, target.marketId)
+    );
+
+    await voidMarket(Math.floor(Date.now() / 1000) + 3600, target.marketId);
+    const refunded = await refund(
+      env.user1,
+      env.userTokenAccounts.user1,
+      target.marketId,
+      target.vault
+    );
+    expect(refunded.toString()).to.equal(position.costBasis.toString());
+
+    // The rolled-over pool is left for the treasurer
+    const marketInfo = await env.program.account.market.fetch(target.market);
+    expect(marketInfo.collateralBalance.toString()).to.equal(rolled.toString());
+  });
+
+  it("Refunding twice should fail", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000)
+    );
+    await voidMarket(Math.floor(Date.now() / 1000) + 3600);
+
+    await refund(env.user1, env.userTokenAccounts.user1);
+
+    try {
+      await refund(env.user1, env.userTokenAccounts.user1);
+      expect.fail("Second refund should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("No refund available for this position");
+    }
+  });
+
+  it("Rewards cannot be claimed from a voided market", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000)
+    );
+    await voidMarket(Math.floor(Date.now() / 1000) + 3600);
+
+    try {
+      await env.program.methods
+        .claimReward()
+        .accounts({
+          user: env.user1.publicKey,
+          userPosition: await env.getUserPosition(env.user1, env.marketId),
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault: env.vault,
+        })
+        .signers([env.user1])
+        .rpc();
+      expect.fail("Claim on a voided market should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Market is voided");
+    }
+  });
+
+  it("Voiding with a past refund deadline should fail", async () => {
+    try {
+      await voidMarket(Math.floor(Date.now() / 1000) - 60);
+      expect.fail("Void with a past deadline should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Refund deadline must be in the future");
+    }
+  });
+
+  it("Non-admin users should not be able to void a market", async () => {
+    try {
+      await env.program.methods
+        .voidMarket(
+          new BN(env.marketId),
+          new BN(Math.floor(Date.now() / 1000) + 3600)
+        )
+        .accounts({
+          authority: env.user1.publicKey,
+        })
+        .signers([env.user1])
+        .rpc();
+      expect.fail("Void by non-admin user should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Only the owner or the resolver can perform this action"
+      );
+    }
+  });
+});
@@ -0,0 +1,234 @@
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { setupTestEnvironment, TestEnv } from "./setup";
+
+describe("No Winner Policy", () => {
+  let env: TestEnv;
+
+  before(async () => {
+    env = await setupTestEnvironment();
+  });
+
+  async function claim(marketId: number, vault, user, tokenAccount) {
+    const before = await env.provider.connection.getTokenAccountBalance(
+      tokenAccount
+    );
+    await env.program.methods
+      .claimReward()
+      .accounts({
+        user: user.publicKey,
+        userPosition: await env.getUserPosition(user, marketId),
+        userTokenAccount: tokenAccount,
+        vault,
+      })
+      .signers([user])
+      .rpc();
+    const after = await env.provider.connection.getTokenAccountBalance(
+      tokenAccount
+    );
+    return new BN(after.value.amount).sub(new BN(before.value.amount));
+  }
+
+  it("Refund policy should return the pool pro rata to cost basis", async () => {
+    const { market, marketId, vault } = await env.createNewMarket({
+      noWinnerPolicy: { refund: {} },
+    });
+
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      1,
+      new BN(100_000_000_000),
+      marketId,
+      vault
+    );
+    await env.buy(
+      env.user2,
+      env.userTokenAccounts.user2,
+      2,
+      new BN(50_000_000_000),
+      marketId,
+      vault
+    );
+
+    const position1 = await env.program.account.userMarketPosition.fetch(
+      await env.getUserPosition(env.user1, marketId)
+    );
+    const position2 = await env.program.account.userMarketPosition.fetch(
+      await env.getUserPosition(env.user2, marketId)
+    );
+
+    // Nobody holds bin 0
+    await env.closeMarket(marketId, 0);
+    const marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.noWinnerPolicy).to.deep.equal({ refund: {} });
+    expect(marketInfo.winningSupply.toString()).to.equal(
+      position1.costBasis.add(position2.costBasis).toString()
+    );
+
+    // Owner cannot withdraw while refunds are outstanding
+    try {
+      await env.program.methods
+        .withdrawCollateral(new BN(marketId))
+        .accounts({
+          authority: env.admin.publicKey,
+          treasuryTokenAccount: env.userTokenAccounts.admin,
+          vault,
+          nextMarket: null,
+          nextVault: null,
+        })
+        .rpc();
+      expect.fail("Withdrawal with outstanding refunds should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Refunds are still outstanding");
+    }
+
+    const refund1 = await claim(
+      marketId,
+      vault,
+      env.user1,
+      env.userTokenAccounts.user1
+    );
+    const refund2 = await claim(
+      marketId,
+      vault,
+      env.user2,
+      env.userTokenAccounts.user2
+    );
+
+    expect(refund1.toString()).to.equal(position1.costBasis.toString());
+    expect(refund1.add(refund2).toString()).to.equal(
+      marketInfo.payoutPool.toString()
+    );
+
+    const marketAfter = await env.program.account.market.fetch(market);
+    expect(marketAfter.remainingSupply.toString()).to.equal("0");
+    expect(marketAfter.collateralBalance.toString()).to.equal("0");
+  });
+
+  it("Roll-over policy should move the pool into the next market", async () => {
+    const { market, marketId, vault } = await env.createNewMarket({
+      noWinnerPolicy: { rollOver: {} },
+    });
+    const next = await env.createNewMarket();
+
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      1,
+      new BN(100_000_000_000),
+      marketId,
+      vault
+    );
+
+    await env.closeMarket(marketId, 0);
+    const marketInfo = await env.program.account.market.fetch(market);
+    const nextBefore = await env.program.account.market.fetch(next.market);
+
+    // Winning claims are not possible
+    try {
+      await claim(marketId, vault, env.user1, env.userTokenAccounts.user1);
+      expect.fail("Claim on an empty winning bin should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Not a winning bin");
+    }
+
+    // Next market accounts are required
+    try {
+      await env.program.methods
+        .withdrawCollateral(new BN(marketId))
+        .accounts({
+          authority: env.admin.publicKey,
+          treasuryTokenAccount: env.userTokenAccounts.admin,
+          vault,
+          nextMarket: null,
+          nextVault: null,
+        })
+        .rpc();
+      expect.fail("Roll-over without next market should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Next market accounts are required");
+    }
+
+    await env.program.methods
+      .withdrawCollateral(new BN(marketId))
+      .accounts({
+        authority: env.admin.publicKey,
+        treasuryTokenAccount: env.userTokenAccounts.admin,
+        vault,
+        nextMarket: next.market,
+        nextVault: next.vault,
+      })
+      .rpc();
+
+    const nextAfter = await env.program.account.market.fetch(next.market);
+    expect(
+      nextAfter.collateralBalance.sub(nextBefore.collateralBalance).toString()
+    ).to.equal(marketInfo.payoutPool.toString());
+  });
+
+  it("Roll-over should target any open market when the following one is closed", async () => {
+    const { market, marketId, vault } = await env.createNewMarket({
+      noWinnerPolicy: { rollOver: {} },
+    });
+    const following = await env.createNewMarket();
+    const open = await env.createNewMarket();
+
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      1,
+      new BN(100_000_000_000),
+      marketId,
+      vault
+    );
+
+    await env.closeMarket(marketId, 0);
+    await env.closeMarket(following.marketId, 0);
+    const marketInfo = await env.program.account.market.fetch(market);
+    const openBefore = await env.program.account.market.fetch(open.market);
+
+    // A resolved market cannot take the pool
+    try {
+      await env.program.methods
+        .withdrawCollateral(new BN(marketId))
+        .accounts({
+          authority: env.admin.publicKey,
+          treasuryTokenAccount: env.userTokenAccounts.admin,
+          vault,
+          nextMarket: following.market,
+          nextVault: following.vault,
+        })
+        .rpc();
+      expect.fail("Roll-over into a closed market should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Market is closed");
+    }
+
+    await env.program.methods
+      .withdrawCollateral(new BN(marketId))
+      .accounts({
+        authority: env.admin.publicKey,
+        treasuryTokenAccount: env.userTokenAccounts.admin,
+        vault,
+        nextMarket: open.market,
+        nextVault: open.vault,
+      })
+      .rpc();
+
+    const openAfter = await env.program.account.market.fetch(open.market);
+    expect(
+      openAfter.collateralBalance.sub(openBefore.collateralBalance).toString()
+    ).to.equal(marketInfo.payoutPool.toString());
+  });
+
+  it("Refund policy without cost basis should fall back to treasury", async () => {
+    const { market, marketId } = await env.createNewMarket({
+      noWinnerPolicy: { refund: {} },
+    });
+
+    await env.closeMarket(marketId, 0);
+    const marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.noWinnerPolicy).to.deep.equal({ treasury: {} });
+  });
+});
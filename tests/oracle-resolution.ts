@@ -0,0 +1,304 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { MockOracle } from "../target/types/mock_oracle";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { setupTestEnvironment, TestEnv, waitUntil } from "./setup";
+
+// Size of the Pyth legacy price fields written by the mock oracle
+const PRICE_ACCOUNT_SIZE = 240;
+
+describe("Oracle Resolution", () => {
+  let env: TestEnv;
+  let oracle: Program<MockOracle>;
+
+  before(async () => {
+    env = await setupTestEnvironment();
+    oracle = anchor.workspace.MockOracle as Program<MockOracle>;
+  });
+
+  async function createPriceFeed(): Promise<anchor.web3.PublicKey> {
+    const feed = Keypair.generate();
+    const lamports =
+      await env.provider.connection.getMinimumBalanceForRentExemption(
+        PRICE_ACCOUNT_SIZE
+      );
+    const tx = new anchor.web3.Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: env.admin.publicKey,
+        newAccountPubkey: feed.publicKey,
+        lamports,
+        space: PRICE_ACCOUNT_SIZE,
+        programId: oracle.programId,
+      })
+    );
+    await env.provider.sendAndConfirm(tx, [feed]);
+    return feed.publicKey;
+  }
+
+  async function setPrice(
+    feed: anchor.web3.PublicKey,
+    price: number,
+    conf: number,
+    expo: number,
+    publishTime: number,
+    trading = true
+  ) {
+    await oracle.methods
+      .setPrice(
+        new BN(price),
+        new BN(conf),
+        expo,
+        new BN(publishTime),
+        trading
+      )
+      .accounts({
+        authority: env.admin.publicKey,
+        priceAccount: feed,
+      })
+      .rpc();
+  }
+
+  // Ticks are whole price units: tick = price / 10^0
+  async function createOracleMarket(feed: anchor.web3.PublicKey) {
+    return env.createNewMarket({
+      oracle: {
+        feed,
+        feedOwner: oracle.programId,
+        format: { pythLegacy: {} },
+        tickExpo: 0,
+        maxConfBps: 100,
+        maxPublishDelay: new BN(60),
+      },
+    });
+  }
+
+  async function resolve(marketId: number, feed: anchor.web3.PublicKey) {
+    await env.program.methods
+      .resolveFromOracle(new BN(marketId))
+      .accounts({
+        proposer: env.user1.publicKey,
+        priceFeed: feed,
+      })
+      .signers([env.user1])
+      .rpc();
+  }
+
+  it("Market should resolve to the bin containing the oracle price", async () => {
+    const feed = await createPriceFeed();
+    const { market, marketId, closeTs } = await createOracleMarket(feed);
+
+    const marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.oracle.feed.toString()).to.equal(feed.toString());
+
+    await waitUntil(closeTs);
+
+    // 123.45 -> tick 123 -> bin (123 - (-360)) / 60 = 8
+    await setPrice(feed, 12345, 10, -2, closeTs + 1);
+    await resolve(marketId, feed);
+
+    const proposed = await env.program.account.market.fetch(market);
+    expect(proposed.closed).to.be.false;
+    expect(proposed.proposedBin).to.equal(8);
+
+    // Oracle proposal is finalized like any other
+    await env.program.methods
+      .finalizeResolution(new BN(marketId))
+      .accounts({
+        finalizer: env.user1.publicKey,
+        seriesPrevious: null,
+      })
+      .signers([env.user1])
+      .rpc();
+
+    const resolved = await env.program.account.market.fetch(market);
+    expect(resolved.closed).to.be.true;
+    expect(resolved.winningBin).to.equal(8);
+  });
+
+  it("Non-positive prices should be rejected and fractions round down", async () => {
+    const feed = await createPriceFeed();
+    const { market, marketId, closeTs } = await createOracleMarket(feed);
+    await waitUntil(closeTs);
+
+    // Price must be positive
+    await setPrice(feed, -12345, 10, -2, closeTs + 1);
+    try {
+      await resolve(marketId, feed);
+      expect.fail("Resolution with a non-positive price should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Oracle price is not available");
+    }
+
+    // 0.59 -> tick 0 -> bin 6
+    await setPrice(feed, 59, 0, -2, closeTs + 1);
+    await resolve(marketId, feed);
+
+    const proposed = await env.program.account.market.fetch(market);
+    expect(proposed.proposedBin).to.equal(6);
+  });
+
+  it("Price published before close_ts should be rejected", async () => {
+    const feed = await createPriceFeed();
+    const { marketId, closeTs } = await createOracleMarket(feed);
+    await waitUntil(closeTs);
+
+    await setPrice(feed, 12345, 10, -2, closeTs - 1);
+    try {
+      await resolve(marketId, feed);
+      expect.fail("Resolution with an early price should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Oracle price was not published within the resolution window"
+      );
+    }
+
+    // Too late
+    await setPrice(feed, 12345, 10, -2, closeTs + 61);
+    try {
+      await resolve(marketId, feed);
+      expect.fail("Resolution with a late price should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Oracle price was not published within the resolution window"
+      );
+    }
+  });
+
+  it("Price with a wide confidence interval should be rejected", async () => {
+    const feed = await createPriceFeed();
+    const { marketId, closeTs } = await createOracleMarket(feed);
+    await waitUntil(closeTs);
+
+    // 1% of 12345 is 123.45
+    await setPrice(feed, 12345, 124, -2, closeTs + 1);
+    try {
+      await resolve(marketId, feed);
+      expect.fail("Resolution with a wide confidence interval should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Oracle price confidence interval is too wide"
+      );
+    }
+  });
+
+  it("Price that is not trading or outside the range should be rejected", async () => {
+    const feed = await createPriceFeed();
+    const { marketId, closeTs } = await createOracleMarket(feed);
+    await waitUntil(closeTs);
+
+    await setPrice(feed, 12345, 10, -2, closeTs + 1, false);
+    try {
+      await resolve(marketId, feed);
+      expect.fail("Resolution with a halted price should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Oracle price is not available");
+    }
+
+    // 1000.00 -> tick 1000, above max_tick
+    await setPrice(feed, 100000, 10, -2, closeTs + 1);
+    try {
+      await resolve(marketId, feed);
+      expect.fail("Resolution outside the market range should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Oracle price is outside the market range"
+      );
+    }
+  });
+
+  it("Prices outside the range should go to the tail bins", async () => {
+    const feed = await createPriceFeed();
+    const { market, marketId, closeTs } = await env.createNewMarket({
+      tailBins: true,
+      oracle: {
+        feed,
+        feedOwner: oracle.programId,
+        format: { pythLegacy: {} },
+        tickExpo: 0,
+        maxConfBps: 100,
+        maxPublishDelay: new BN(60),
+      },
+    });
+    await waitUntil(closeTs);
+
+    // 1000.00 -> tick 1000, above the last bin [360, 420)
+    await setPrice(feed, 100000, 10, -2, closeTs + 1);
+    await resolve(marketId, feed);
+
+    const marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.proposedBin).to.equal(14);
+  });
+
+  it("Wrong feed or unconfigured market should be rejected", async () => {
+    const feed = await createPriceFeed();
+    const otherFeed = await createPriceFeed();
+    const { marketId, closeTs } = await createOracleMarket(feed);
+    const plain = await env.createNewMarket();
+    await waitUntil(Math.max(closeTs, plain.closeTs));
+
+    await setPrice(otherFeed, 12345, 10, -2, closeTs + 1);
+    try {
+      await resolve(marketId, otherFeed);
+      expect.fail("Resolution with a different feed should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Invalid oracle price account");
+    }
+
+    // Feed must be owned by the configured program
+    const misowned = await env.createNewMarket({
+      oracle: {
+        feed,
+        feedOwner: SystemProgram.programId,
+        format: { pythLegacy: {} },
+        tickExpo: 0,
+        maxConfBps: 100,
+        maxPublishDelay: new BN(60),
+      },
+    });
+    await waitUntil(misowned.closeTs);
+    await setPrice(feed, 12345, 10, -2, misowned.closeTs + 1);
+    try {
+      await resolve(misowned.marketId, feed);
+      expect.fail("Resolution from a feed with another owner should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Invalid oracle price account");
+    }
+
+    try {
+      await resolve(plain.marketId, feed);
+      expect.fail("Resolution of a market without an oracle should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Market is not configured for oracle resolution"
+      );
+    }
+  });
+
+  it("Resolution before close_ts should fail", async () => {
+    const feed = await createPriceFeed();
+    const closeTime = Math.floor(Date.now() / 1000) + 3600;
+    const { marketId } = await env.createNewMarket({
+      closeTime,
+      oracle: {
+        feed,
+        feedOwner: oracle.programId,
+        format: { pythLegacy: {} },
+        tickExpo: 0,
+        maxConfBps: 100,
+        maxPublishDelay: new BN(60),
+      },
+    });
+
+    await setPrice(feed, 12345, 10, -2, closeTime);
+    try {
+      await resolve(marketId, feed);
+      expect.fail("Resolution before close_ts should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Market cannot be resolved before its close time"
+      );
+    }
+  });
+});
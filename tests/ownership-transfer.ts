@@ -0,0 +1,105 @@
+import { expect } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import { setupTestEnvironment, TestEnv } from "./setup";
+
+describe("Ownership Transfer", () => {
+  let env: TestEnv;
+
+  before(async () => {
+    env = await setupTestEnvironment();
+  });
+
+  async function proposeOwner(owner, newOwner: PublicKey) {
+    const builder = env.program.methods.proposeOwner(newOwner).accounts({
+      owner: owner.publicKey,
+    });
+    await (owner === env.admin ? builder : builder.signers([owner])).rpc();
+  }
+
+  async function acceptOwnership(newOwner) {
+    const builder = env.program.methods.acceptOwnership().accounts({
+      newOwner: newOwner.publicKey,
+    });
+    await (newOwner === env.admin ? builder : builder.signers([newOwner])).rpc();
+  }
+
+  it("Proposed owner should take over ownership and the owner's roles", async () => {
+    await proposeOwner(env.admin, env.user1.publicKey);
+
+    let state = await env.program.account.programState.fetch(env.programState);
+    expect(state.pendingOwner.toString()).to.equal(
+      env.user1.publicKey.toString()
+    );
+    // Nothing changes until the transfer is accepted
+    expect(state.owner.toString()).to.equal(env.admin.publicKey.toString());
+
+    await acceptOwnership(env.user1);
+
+    state = await env.program.account.programState.fetch(env.programState);
+    expect(state.owner.toString()).to.equal(env.user1.publicKey.toString());
+    expect(state.pendingOwner.toString()).to.equal(
+      PublicKey.default.toString()
+    );
+    expect(state.treasury.toString()).to.equal(env.user1.publicKey.toString());
+
+    // Previous owner lost its powers
+    try {
+      await proposeOwner(env.admin, env.admin.publicKey);
+      expect.fail("Previous owner should not propose owners");
+    } catch (e) {
+      expect(e.toString()).to.include("Owner only function");
+    }
+
+    // Hand ownership back for other tests
+    await proposeOwner(env.user1, env.admin.publicKey);
+    await acceptOwnership(env.admin);
+
+    state = await env.program.account.programState.fetch(env.programState);
+    expect(state.owner.toString()).to.equal(env.admin.publicKey.toString());
+    expect(state.treasury.toString()).to.equal(env.admin.publicKey.toString());
+  });
+
+  it("Only the pending owner can accept ownership", async () => {
+    await proposeOwner(env.admin, env.user1.publicKey);
+
+    try {
+      await acceptOwnership(env.user2);
+      expect.fail("Accepting by another user should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Only the pending owner can accept ownership"
+      );
+    } finally {
+      await env.program.methods
+        .cancelOwnershipTransfer()
+        .accounts({
+          owner: env.admin.publicKey,
+        })
+        .rpc();
+    }
+  });
+
+  it("Cancelled transfer cannot be accepted", async () => {
+    await proposeOwner(env.admin, env.user1.publicKey);
+    await env.program.methods
+      .cancelOwnershipTransfer()
+      .accounts({
+        owner: env.admin.publicKey,
+      })
+      .rpc();
+
+    const state = await env.program.account.programState.fetch(
+      env.programState
+    );
+    expect(state.pendingOwner.toString()).to.equal(
+      PublicKey.default.toString()
+    );
+
+    try {
+      await acceptOwnership(env.user1);
+      expect.fail("Accepting a cancelled transfer should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("No ownership transfer is pending");
+    }
+  });
+});
@@ -0,0 +1,211 @@
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { setupTestEnvironment, TestEnv } from "./setup";
+
+describe("Position Close", () => {
+  let env: TestEnv;
+
+  before(async () => {
+    env = await setupTestEnvironment();
+  });
+
+  beforeEach(async () => {
+    // Initialize market for each test
+    await env.resetMarket();
+  });
+
+  async function closePosition(user, marketId = env.marketId) {
+    await env.program.methods
+      .closePosition()
+      .accounts({
+        user: user.publicKey,
+        userPosition: await env.getUserPosition(user, marketId),
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  it("Position with an unclaimed payout cannot be closed", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000)
+    );
+    await env.closeMarket(env.marketId, 0);
+
+    try {
+      await closePosition(env.user1);
+      expect.fail("Closing with an unclaimed payout should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Position still has a payout or refund to claim"
+      );
+    }
+  });
+
+  it("Position cannot be closed before the market is closed", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      1,
+      new BN(100_000_000_000)
+    );
+
+    try {
+      await closePosition(env.user1);
+      expect.fail("Closing in an open market should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Market is not closed");
+    }
+  });
+
+  it("Claimed position should close and return its rent", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000)
+    );
+    await env.closeMarket(env.marketId, 0);
+
+    const userPosition = await env.getUserPosition(env.user1, env.marketId);
+    await env.program.methods
+      .claimReward()
+      .accounts({
+        user: env.user1.publicKey,
+        userPosition: userPosition,
+        userTokenAccount: env.userTokenAccounts.user1,
+        vault: env.vault,
+      })
+      .signers([env.user1])
+      .rpc();
+
+    const rent = await env.provider.connection.getBalance(userPosition);
+    const before = await env.provider.connection.getBalance(
+      env.user1.publicKey
+    );
+    await closePosition(env.user1);
+    const after = await env.provider.connection.getBalance(
+      env.user1.publicKey
+    );
+
+    expect(await env.provider.connection.getAccountInfo(userPosition)).to.be
+      .null;
+    // Rent returned minus the transaction fee
+    expect(after - before).to.be.greaterThan(rent - 10_000);
+  });
+
+  it("Position should close after its market is finalized", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      1,
+      new BN(100_000_000_000)
+    );
+    await env.closeMarket(env.marketId, 0);
+
+    await env.program.methods
+      .finalizeMarket(new BN(env.marketId))
+      .accounts({
+        authority: env.admin.publicKey,
+        treasuryTokenAccount: env.userTokenAccounts.admin,
+        vault: env.vault,
+      })
+      .rpc();
+    expect(await env.provider.connection.getAccountInfo(env.market)).to.be
+      .null;
+
+    const userPosition = await env.getUserPosition(env.user1, env.marketId);
+    await closePosition(env.user1);
+
+    expect(await env.provider.connection.getAccountInfo(userPosition)).to.be
+      .null;
+  });
+
+  it("Losing positions across markets should close in one batch", async () => {
+    const first = { marketId: env.marketId, market: env.market };
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      1,
+      new BN(100_000_000_000)
+    );
+
+    const second = await env.createNewMarket();
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      1,
+      new BN(100_000_000_000),
+      second.marketId,
+      second.vault
+    );
+
+    await env.closeMarket(first.marketId, 0);
+    await env.closeMarket(second.marketId, 0);
+
+    const firstPosition = await env.getUserPosition(
+      env.user1,
+      first.marketId
+    );
+    const secondPosition = await env.getUserPosition(
+      env.user1,
+      second.marketId
+    );
+
+    await env.program.methods
+      .closePositions()
+      .accounts({
+        user: env.user1.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: first.market, isWritable: false, isSigner: false },
+        { pubkey: firstPosition, isWritable: true, isSigner: false },
+        { pubkey: second.market, isWritable: false, isSigner: false },
+        { pubkey: secondPosition, isWritable: true, isSigner: false },
+      ])
+      .signers([env.user1])
+      .rpc();
+
+    expect(await env.provider.connection.getAccountInfo(firstPosition)).to.be
+      .null;
+    expect(await env.provider.connection.getAccountInfo(secondPosition)).to
+      .be.null;
+  });
+
+  it("Batch close should reject a position paired with the wrong market", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      1,
+      new BN(100_000_000_000)
+    );
+    const other = await env.createNewMarket();
+    await env.closeMarket(env.marketId, 0);
+    await env.closeMarket(other.marketId, 0);
+
+    try {
+      await env.program.methods
+        .closePositions()
+        .accounts({
+          user: env.user1.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: other.market, isWritable: false, isSigner: false },
+          {
+            pubkey: await env.getUserPosition(env.user1, env.marketId),
+            isWritable: true,
+            isSigner: false,
+          },
+        ])
+        .signers([env.user1])
+        .rpc();
+      expect.fail("Mismatched market should be rejected");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Market account does not match the position"
+      );
+    }
+  });
+});
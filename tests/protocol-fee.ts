@@ -0,0 +1,215 @@
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { setupTestEnvironment, TestEnv } from "./setup";
+
+describe("Protocol Fee", () => {
+  let env: TestEnv;
+
+  before(async () => {
+    env = await setupTestEnvironment();
+  });
+
+  beforeEach(async () => {
+    // Initialize market for each test
+    await env.resetMarket();
+  });
+
+  async function setMarketFee(feeBps: number) {
+    await env.program.methods
+      .setMarketFee(new BN(env.marketId), feeBps)
+      .accounts({
+        owner: env.admin.publicKey,
+      })
+      .rpc();
+  }
+
+  // Fee on `amount` at `feeBps`, rounded up
+  function feeFor(amount: BN, feeBps: number): BN {
+    return amount.muln(feeBps).addn(9_999).divn(10_000);
+  }
+
+  it("Buys should charge the fee on top of the cost", async () => {
+    await setMarketFee(100); // 1%
+
+    const before = await env.balance(env.userTokenAccounts.user1);
+    await env.program.methods
+      .buyTokens(
+        new BN(env.marketId),
+        [0],
+        [new BN(100_000_000_000)],
+        new BN(1_000_000_000_000)
+      )
+      .accounts({
+        user: env.user1.publicKey,
+        userTokenAccount: env.userTokenAccounts.user1,
+        vault: env.vault,
+      })
+      .signers([env.user1])
+      .rpc();
+    const paid = before.sub(await env.balance(env.userTokenAccounts.user1));
+
+    const marketInfo = await env.program.account.market.fetch(env.market);
+    const cost = marketInfo.collateralBalance;
+    expect(marketInfo.feesAccrued.toString()).to.equal(
+      feeFor(cost, 100).toString()
+    );
+    expect(paid.toString()).to.equal(
+      cost.add(marketInfo.feesAccrued).toString()
+    );
+  });
+
+  it("Sells should deduct the fee from the revenue", async () => {
+    await setMarketFee(100);
+
+    await env.program.methods
+      .buyTokens(
+        new BN(env.marketId),
+        [0],
+        [new BN(100_000_000_000)],
+        new BN(1_000_000_000_000)
+      )
+      .accounts({
+        user: env.user1.publicKey,
+        userTokenAccount: env.userTokenAccounts.user1,
+        vault: env.vault,
+      })
+      .signers([env.user1])
+      .rpc();
+    const afterBuy = await env.program.account.market.fetch(env.market);
+
+    const before = await env.balance(env.userTokenAccounts.user1);
+    await env.program.methods
+      .sellTokens(new BN(env.marketId), [0], [new BN(50_000_000_000)], new BN(0))
+      .accounts({
+        user: env.user1.publicKey,
+        userTokenAccount: env.userTokenAccounts.user1,
+        vault: env.vault,
+      })
+      .signers([env.user1])
+      .rpc();
+    const received = (await env.balance(env.userTokenAccounts.user1)).sub(before);
+
+    const afterSell = await env.program.account.market.fetch(env.market);
+    const revenue = afterBuy.collateralBalance.sub(afterSell.collateralBalance);
+    const sellFee = afterSell.feesAccrued.sub(afterBuy.feesAccrued);
+    expect(sellFee.toString()).to.equal(
+      feeFor(revenue, 100).toString()
+    );
+    expect(received.toString()).to.equal(revenue.sub(sellFee).toString());
+
+    // The ledger records the amount paid out, not the gross revenue
+    const position = await env.program.account.userMarketPosition.fetch(
+      await env.getUserPosition(env.user1, env.marketId)
+    );
+    expect(position.bins[0].received.toString()).to.equal(received.toString());
+  });
+
+  it("Smallest trades should still pay a fee", async () => {
+    await setMarketFee(100);
+
+    await env.program.methods
+      .buyTokens(new BN(env.marketId), [0], [new BN(1)], new BN(1_000))
+      .accounts({
+        user: env.user1.publicKey,
+        userTokenAccount: env.userTokenAccounts.user1,
+        vault: env.vault,
+      })
+      .signers([env.user1])
+      .rpc();
+
+    const marketInfo = await env.program.account.market.fetch(env.market);
+    expect(marketInfo.collateralBalance.toNumber()).to.be.greaterThan(0);
+    expect(marketInfo.feesAccrued.toNumber()).to.be.at.least(1);
+  });
+
+  it("Treasury should collect accrued fees", async () => {
+    await setMarketFee(200);
+
+    await env.program.methods
+      .buyTokens(
+        new BN(env.marketId),
+        [1],
+        [new BN(100_000_000_000)],
+        new BN(1_000_000_000_000)
+      )
+      .accounts({
+        user: env.user1.publicKey,
+        userTokenAccount: env.userTokenAccounts.user1,
+        vault: env.vault,
+      })
+      .signers([env.user1])
+      .rpc();
+    const marketInfo = await env.program.account.market.fetch(env.market);
+
+    // Only the owner or the treasurer may collect
+    try {
+      await env.program.methods
+        .collectFees(new BN(env.marketId))
+        .accounts({
+          authority: env.user1.publicKey,
+          treasuryTokenAccount: env.userTokenAccounts.user1,
+          vault: env.vault,
+          creatorTokenAccount: null,
+        })
+        .signers([env.user1])
+        .rpc();
+      expect.fail("Non-treasury collection should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Only the owner or the treasurer can perform this action"
+      );
+    }
+
+    // Owner holds the treasurer role by default
+    const before = await env.balance(env.userTokenAccounts.admin);
+    await env.program.methods
+      .collectFees(new BN(env.marketId))
+      .accounts({
+        authority: env.admin.publicKey,
+        treasuryTokenAccount: env.userTokenAccounts.admin,
+        vault: env.vault,
+        creatorTokenAccount: null,
+      })
+      .rpc();
+    const collected = (await env.balance(env.userTokenAccounts.admin)).sub(before);
+
+    expect(collected.toString()).to.equal(marketInfo.feesAccrued.toString());
+    const marketAfter = await env.program.account.market.fetch(env.market);
+    expect(marketAfter.feesAccrued.toString()).to.equal("0");
+    expect(marketAfter.collateralBalance.toString()).to.equal(
+      marketInfo.collateralBalance.toString()
+    );
+  });
+
+  it("New markets should use the program default fee", async () => {
+    await env.program.methods
+      .setDefaultFee(25)
+      .accounts({
+        owner: env.admin.publicKey,
+      })
+      .rpc();
+
+    try {
+      const { market } = await env.createNewMarket();
+      const marketInfo = await env.program.account.market.fetch(market);
+      expect(marketInfo.feeBps).to.equal(25);
+    } finally {
+      // Restore the default for other tests
+      await env.program.methods
+        .setDefaultFee(0)
+        .accounts({
+          owner: env.admin.publicKey,
+        })
+        .rpc();
+    }
+  });
+
+  it("Fees above the maximum should be rejected", async () => {
+    try {
+      await setMarketFee(1_001);
+      expect.fail("Fee above the maximum should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Fee exceeds the maximum");
+    }
+  });
+});
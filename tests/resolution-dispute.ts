@@ -0,0 +1,215 @@
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { setupTestEnvironment, TestEnv, waitUntil } from "./setup";
+
+describe("Resolution Dispute Window", () => {
+  let env: TestEnv;
+
+  // Long enough to override, short enough to wait out in tests
+  const DISPUTE_WINDOW = 10;
+
+  before(async () => {
+    env = await setupTestEnvironment();
+  });
+
+  after(async () => {
+    // Restore the owner as resolver for other test files
+    await env.program.methods
+      .revokeRole({ resolver: {} })
+      .accounts({
+        owner: env.admin.publicKey,
+      })
+      .rpc();
+  });
+
+  async function propose(marketId: number, winningBin: number, authority?) {
+    const signer = authority ?? env.admin.payer;
+    await env.program.methods
+      .proposeResolution(new BN(marketId), winningBin)
+      .accounts({
+        authority: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function override(marketId: number, winningBin: number, authority?) {
+    const signer = authority ?? env.admin.payer;
+    await env.program.methods
+      .overrideResolution(new BN(marketId), winningBin)
+      .accounts({
+        authority: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function finalize(marketId: number) {
+    await env.program.methods
+      .finalizeResolution(new BN(marketId))
+      .accounts({
+        finalizer: env.user1.publicKey,
+        seriesPrevious: null,
+      })
+      .signers([env.user1])
+      .rpc();
+  }
+
+  it("Proposal should be overridable until the challenge deadline", async () => {
+    const { market, marketId, vault, closeTs } = await env.createNewMarket({
+      disputeWindow: DISPUTE_WINDOW,
+    });
+
+    await env.program.methods
+      .buyTokens(
+        new BN(marketId),
+        [1],
+        [new BN(100_000_000_000)],
+        new BN(1_000_000_000_000)
+      )
+      .accounts({
+        user: env.user1.publicKey,
+        userTokenAccount: env.userTokenAccounts.user1,
+        vault,
+      })
+      .signers([env.user1])
+      .rpc();
+
+    await waitUntil(closeTs);
+
+    // Fat-fingered proposal
+    await propose(marketId, 0);
+    const proposed = await env.program.account.market.fetch(market);
+    expect(proposed.proposedBin).to.equal(0);
+    expect(proposed.closed).to.be.false;
+    expect(proposed.challengeDeadline.toNumber()).to.be.greaterThan(closeTs);
+
+    // Proposing again is not allowed
+    try {
+      await propose(marketId, 1);
+      expect.fail("Second proposal should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "A resolution has already been proposed"
+      );
+    }
+
+    // Claims stay locked until finalization
+    try {
+      await env.program.methods
+        .claimReward()
+        .accounts({
+          user: env.user1.publicKey,
+          userPosition: await env.getUserPosition(env.user1, marketId),
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault,
+        })
+        .signers([env.user1])
+        .rpc();
+      expect.fail("Claim before finalization should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Market is not active");
+    }
+
+    // Finalization waits for the challenge window
+    try {
+      await finalize(marketId);
+      expect.fail("Finalization inside the challenge window should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Challenge window is still open");
+    }
+
+    await override(marketId, 1);
+    const overridden = await env.program.account.market.fetch(market);
+    expect(overridden.proposedBin).to.equal(1);
+
+    await waitUntil(proposed.challengeDeadline.toNumber());
+
+    // Window has closed
+    try {
+      await override(marketId, 2);
+      expect.fail("Override after the challenge deadline should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Challenge window has ended");
+    }
+
+    await finalize(marketId);
+    const finalized = await env.program.account.market.fetch(market);
+    expect(finalized.closed).to.be.true;
+    expect(finalized.winningBin).to.equal(1);
+    expect(finalized.proposedBin).to.be.null;
+  });
+
+  it("Designated resolver should be able to propose and override", async () => {
+    await env.program.methods
+      .grantRole({ resolver: {} }, env.user2.publicKey)
+      .accounts({
+        owner: env.admin.publicKey,
+      })
+      .rpc();
+
+    const { market, marketId, closeTs } = await env.createNewMarket({
+      disputeWindow: DISPUTE_WINDOW,
+    });
+    await waitUntil(closeTs);
+
+    await propose(marketId, 0, env.user2);
+    await override(marketId, 2, env.user2);
+
+    const marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.proposedBin).to.equal(2);
+  });
+
+  it("Other users should not be able to propose or override", async () => {
+    const { marketId, closeTs } = await env.createNewMarket({
+      disputeWindow: DISPUTE_WINDOW,
+    });
+    await waitUntil(closeTs);
+
+    try {
+      await propose(marketId, 0, env.user3);
+      expect.fail("Proposal by another user should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Only the owner or the resolver can perform this action"
+      );
+    }
+
+    await propose(marketId, 0);
+
+    try {
+      await override(marketId, 1, env.user3);
+      expect.fail("Override by another user should fail");
+    } catch (e) {
+      expect(e.toString()).to.include(
+        "Only the owner or the resolver can perform this action"
+      );
+    }
+
+    // Only the owner can grant roles
+    try {
+      await env.program.methods
+        .grantRole({ resolver: {} }, env.user3.publicKey)
+        .accounts({
+          owner: env.user3.publicKey,
+        })
+        .signers([env.user3])
+        .rpc();
+      expect.fail("Setting the resolver by another user should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Owner only function");
+    }
+  });
+
+  it("Finalizing without a proposal should fail", async () => {
+    const { marketId, closeTs } = await env.createNewMarket();
+    await waitUntil(closeTs);
+
+    try {
+      await finalize(marketId);
+      expect.fail("Finalization without a proposal should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("No resolution has been proposed");
+    }
+  });
+});
@@ -0,0 +1,116 @@
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { setupTestEnvironment, TestEnv } from "./setup";
+
+describe("Reward Claim", () => {
+  let env: TestEnv;
+
+  before(async () => {
+    env = await setupTestEnvironment();
+  });
+
+  beforeEach(async () => {
+    // Initialize market for each test
+    await env.resetMarket();
+  });
+
+  async function claim(user, tokenAccount): Promise<BN> {
+    const before = await env.provider.connection.getTokenAccountBalance(
+      tokenAccount
+    );
+    await env.program.methods
+      .claimReward()
+      .accounts({
+        user: user.publicKey,
+        userPosition: await env.getUserPosition(user, env.marketId),
+        userTokenAccount: tokenAccount,
+        vault: env.vault,
+      })
+      .signers([user])
+      .rpc();
+    const after = await env.provider.connection.getTokenAccountBalance(
+      tokenAccount
+    );
+    return new BN(after.value.amount).sub(new BN(before.value.amount));
+  }
+
+  it("Close should snapshot the payout pool and winning supply", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000)
+    );
+    await env.buy(
+      env.user2,
+      env.userTokenAccounts.user2,
+      1,
+      new BN(50_000_000_000)
+    );
+
+    const marketBefore = await env.program.account.market.fetch(env.market);
+    const binsBefore = await env.getMarketBins(env.marketId);
+    await env.closeMarket(env.marketId, 0);
+    const marketAfter = await env.program.account.market.fetch(env.market);
+
+    expect(marketAfter.payoutPool.toString()).to.equal(
+      marketBefore.collateralBalance.toString()
+    );
+    expect(marketAfter.winningSupply.toString()).to.equal(
+      binsBefore[0].toString()
+    );
+    expect(marketAfter.remainingSupply.toString()).to.equal(
+      marketAfter.winningSupply.toString()
+    );
+    expect(marketAfter.remainingPayout.toString()).to.equal(
+      marketAfter.payoutPool.toString()
+    );
+  });
+
+  it("Equal winning positions should receive equal payouts regardless of claim order", async () => {
+    const amount = new BN(100_000_000_000);
+    await env.buy(env.user1, env.userTokenAccounts.user1, 0, amount);
+    await env.buy(env.user2, env.userTokenAccounts.user2, 0, amount);
+    await env.buy(env.user3, env.userTokenAccounts.user3, 1, amount);
+
+    await env.closeMarket(env.marketId, 0);
+    const market = await env.program.account.market.fetch(env.market);
+
+    const reward1 = await claim(env.user1, env.userTokenAccounts.user1);
+    const reward2 = await claim(env.user2, env.userTokenAccounts.user2);
+
+    // Both winners hold half of the winning supply
+    expect(reward1.toString()).to.equal(
+      market.payoutPool.div(new BN(2)).toString()
+    );
+
+    // Last claimer receives exactly the residual
+    expect(reward1.add(reward2).toString()).to.equal(
+      market.payoutPool.toString()
+    );
+    expect(reward2.sub(reward1).lte(new BN(1))).to.be.true;
+
+    const marketAfter = await env.program.account.market.fetch(env.market);
+    expect(marketAfter.remainingSupply.toString()).to.equal("0");
+    expect(marketAfter.remainingPayout.toString()).to.equal("0");
+  });
+
+  it("Claiming twice should fail", async () => {
+    await env.buy(
+      env.user1,
+      env.userTokenAccounts.user1,
+      0,
+      new BN(100_000_000_000)
+    );
+    await env.closeMarket(env.marketId, 0);
+
+    await claim(env.user1, env.userTokenAccounts.user1);
+
+    try {
+      await claim(env.user1, env.userTokenAccounts.user1);
+      expect.fail("Second claim should fail");
+    } catch (e) {
+      expect(e.toString()).to.include("Not a winning bin");
+    }
+  });
+});
@@ -7,7 +7,7 @@ import {
   createMint,
   createAssociatedTokenAccount,
   mintTo,
-  createAccount,
+  getAssociatedTokenAddressSync,
 } from "@solana/spl-token";
 
 export interface TestEnv {
@@ -34,6 +34,7 @@ export interface TestEnv {
     user: Keypair,
     marketId: number
   ) => Promise<anchor.web3.PublicKey>;
+  getMarketBins: (marketId: number) => Promise<typeof BN.prototype[]>;
   userTokenAccounts: {
     admin: anchor.web3.PublicKey;
     user1: anchor.web3.PublicKey;
@@ -49,15 +50,54 @@ export interface TestEnv {
     minTick?: number;
     maxTick?: number;
     closeTime?: number;
+    noWinnerPolicy?: any;
+    tradingCutoff?: number;
+    seriesPrevious?: number;
+    oracle?: any;
+    disputeWindow?: number;
+    tailBins?: boolean;
+    claimWindow?: number;
   }) => Promise<{
     market: anchor.web3.PublicKey;
     marketId: number;
+    vault: anchor.web3.PublicKey;
+    closeTs: number;
   }>;
-  replenishTokens: (user: Keypair, amount?: number) => Promise<void>;
-  closeMarketsSequentially: (
-    targetMarketId: number,
-    winningBin: number
+  buy: (
+    user: Keypair,
+    tokenAccount: anchor.web3.PublicKey,
+    binIndex: number,
+    amount: typeof BN.prototype,
+    marketId?: number,
+    vault?: anchor.web3.PublicKey
   ) => Promise<void>;
+  balance: (tokenAccount: anchor.web3.PublicKey) => Promise<typeof BN.prototype>;
+  replenishTokens: (user: Keypair, amount?: number) => Promise<void>;
+  closeMarket: (marketId: number, winningBin?: number) => Promise<void>;
+}
+
+/**
+ * Test markets close shortly after creation so they can be resolved
+ * (resolution requires close_ts to have passed)
+ */
+export const MARKET_DURATION = 15; // seconds
+
+/**
+ * Close time for a test market created now
+ */
+export function testCloseTime(): number {
+  return Math.floor(Date.now() / 1000) + MARKET_DURATION;
+}
+
+/**
+ * Wait until the given unix timestamp has passed (with a margin for
+ * validator clock drift)
+ */
+export async function waitUntil(timestamp: number): Promise<void> {
+  const waitMs = (timestamp + 2) * 1000 - Date.now();
+  if (waitMs > 0) {
+    await new Promise((resolve) => setTimeout(resolve, waitMs));
+  }
 }
 
 /**
@@ -222,7 +262,7 @@ export async function setupTestEnvironment(): Promise<TestEnv> {
   const tickSpacing = 60;
   const minTick = -360;
   const maxTick = 360;
-  const closeTime = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60; // 1 week later
+  const closeTime = testCloseTime();
 
   // Calculate vault authority PDA for the first market ID (0)
   let marketId = 0;
@@ -232,14 +272,11 @@ export async function setupTestEnvironment(): Promise<TestEnv> {
       program.programId
     );
 
-  // Create token account owned by the PDA (admin pays)
-  // Here, only the account is created, and funds are filled by the user through buyTokens
-  const vault = await createAccount(
-    provider.connection,
-    admin.payer,
+  // Market vault is the PDA's associated token account (created by createMarket)
+  const vault = getAssociatedTokenAddressSync(
     collateralMint,
     vaultAuthority, // PDA is owner
-    Keypair.generate() // Create new account keypair
+    true // Allow off-curve owner
   );
 
   // Market account address (PDA) calculation
@@ -260,15 +297,23 @@ export async function setupTestEnvironment(): Promise<TestEnv> {
       // Create market if it doesn't exist
       console.log("🔨 Creating Market ID", marketId, "...");
       await program.methods
-        .createMarket(
+        .createMarket({
           tickSpacing,
-          new BN(minTick),
-          new BN(maxTick),
-          new BN(closeTime)
-        )
+          minTick: new BN(minTick),
+          maxTick: new BN(maxTick),
+          closeTs: new BN(closeTime),
+          noWinnerPolicy: { treasury: {} },
+          tradingCutoff: new BN(0),
+          seriesPrevious: null,
+          oracle: null,
+          disputeWindow: new BN(0),
+          tailBins: false,
+          claimWindow: new BN(0),
+        })
         .accounts({
-          owner: admin.publicKey,
+          authority: admin.publicKey,
           collateralMint: collateralMint,
+          creatorTokenAccount: null,
         })
         .rpc();
       console.log("✅ Market ID", marketId, "created!");
@@ -293,6 +338,24 @@ export async function setupTestEnvironment(): Promise<TestEnv> {
     return userPosition;
   }
 
+  // Bin quantities of a market (zero-copy bins account: 8-byte discriminator,
+  // market_id, allocated count, padding, then one little-endian u64 per bin)
+  async function getMarketBins(marketId: number) {
+    const [marketBins] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bins"), new BN(marketId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const info = await provider.connection.getAccountInfo(marketBins);
+    const allocated = info.data.readUInt32LE(16);
+
+    const bins = [];
+    for (let i = 0; i < allocated; i++) {
+      const offset = 24 + 8 * i;
+      bins.push(new BN(info.data.subarray(offset, offset + 8), "le"));
+    }
+    return bins;
+  }
+
   // Market reset function (create new market to provide clean test environment)
   async function resetMarketInternal() {
     try {
@@ -332,6 +395,13 @@ export async function setupTestEnvironment(): Promise<TestEnv> {
     minTick?: number;
     maxTick?: number;
     closeTime?: number;
+    noWinnerPolicy?: any;
+    tradingCutoff?: number;
+    seriesPrevious?: number;
+    oracle?: any;
+    disputeWindow?: number;
+    tailBins?: boolean;
+    claimWindow?: number;
   }) {
     // Get current market count from program state
     const state = await program.account.programState.fetch(programState);
@@ -353,13 +423,11 @@ export async function setupTestEnvironment(): Promise<TestEnv> {
         program.programId
       );
 
-    // Create token vault for new market
-    const newVault = await createAccount(
-      provider.connection,
-      admin.payer,
+    // Token vault for new market (created by createMarket)
+    const newVault = getAssociatedTokenAddressSync(
       collateralMint,
       newVaultAuthority,
-      Keypair.generate()
+      true
     );
 
     console.log(
@@ -369,16 +437,25 @@ export async function setupTestEnvironment(): Promise<TestEnv> {
 
     // Create new market
     console.log("🔨 Creating new market ID", newMarketId, "...");
+    const newCloseTs = params?.closeTime ?? testCloseTime();
     await program.methods
-      .createMarket(
-        params?.tickSpacing ?? tickSpacing,
-        new BN(params?.minTick ?? minTick),
-        new BN(params?.maxTick ?? maxTick),
-        new BN(params?.closeTime ?? closeTime)
-      )
+      .createMarket({
+        tickSpacing: params?.tickSpacing ?? tickSpacing,
+        minTick: new BN(params?.minTick ?? minTick),
+        maxTick: new BN(params?.maxTick ?? maxTick),
+        closeTs: new BN(newCloseTs),
+        noWinnerPolicy: params?.noWinnerPolicy ?? { treasury: {} },
+        tradingCutoff: new BN(params?.tradingCutoff ?? 0),
+        seriesPrevious: params?.seriesPrevious != null ? new BN(params.seriesPrevious) : null,
+        oracle: params?.oracle ?? null,
+        disputeWindow: new BN(params?.disputeWindow ?? 0),
+        tailBins: params?.tailBins ?? false,
+        claimWindow: new BN(params?.claimWindow ?? 0),
+      })
       .accounts({
-        owner: admin.publicKey,
+        authority: admin.publicKey,
         collateralMint: collateralMint,
+        creatorTokenAccount: null,
       })
       .rpc();
     console.log("✅ New market ID", newMarketId, "created!");
@@ -394,6 +471,7 @@ export async function setupTestEnvironment(): Promise<TestEnv> {
       vault: newVault,
       vaultAuthority: newVaultAuthority,
       vaultAuthorityBump: newVaultAuthorityBump,
+      closeTs: newCloseTs,
     };
   }
 
@@ -452,6 +530,7 @@ export async function setupTestEnvironment(): Promise<TestEnv> {
     maxTick,
     closeTime: new BN(closeTime),
     getUserPosition,
+    getMarketBins,
     userTokenAccounts: {
       admin: adminTokenAccount,
       user1: user1TokenAccount,
@@ -470,62 +549,68 @@ export async function setupTestEnvironment(): Promise<TestEnv> {
       testEnv.vaultAuthorityBump = updatedVaultAuthorityBump;
     },
     createNewMarket,
-    replenishTokens,
-    closeMarketsSequentially: async (
-      targetMarketId: number,
-      winningBin: number = 0
+    // Buy `amount` tokens of one bin (defaults to the current test market)
+    buy: async (
+      user: Keypair,
+      tokenAccount: anchor.web3.PublicKey,
+      binIndex: number,
+      amount: typeof BN.prototype,
+      marketId: number = testEnv.marketId,
+      vault: anchor.web3.PublicKey = testEnv.vault
     ) => {
-      // Check program state for last_closed_market value
-      const state = await program.account.programState.fetch(programState);
-      let lastClosed = state.lastClosedMarket
-        ? state.lastClosedMarket.toNumber()
-        : -1;
-
-      // Markets must close sequentially, so close from last_closed_market+1 to target market
-      for (let id = lastClosed + 1; id <= targetMarketId; id++) {
-        try {
-          // Calculate market account address
-          const [marketToClose] =
-            await anchor.web3.PublicKey.findProgramAddressSync(
-              [Buffer.from("market"), new BN(id).toArrayLike(Buffer, "le", 8)],
-              program.programId
-            );
-
-          console.log(`Closing market ID ${id}...`);
-
-          // Check market information
-          try {
-            const marketInfo = await program.account.market.fetch(
-              marketToClose
-            );
-
-            // Skip already closed markets
-            if (marketInfo.closed) {
-              console.log(`Market ID ${id} is already closed.`);
-              continue;
-            }
-
-            // Close market
-            const closeBin = id === targetMarketId ? winningBin : 0; // Only target market is specified with winning bin
-            await program.methods
-              .closeMarket(new BN(id), closeBin)
-              .accounts({
-                owner: admin.publicKey,
-              })
-              .rpc();
-
-            console.log(`Market ID ${id} closed successfully.`);
-          } catch (e) {
-            // If market doesn't exist, show warning and continue
-            console.log(
-              `Market ID ${id} doesn't exist or error occurred during processing: ${e.message}`
-            );
-          }
-        } catch (e) {
-          console.error(`Failed to close market ID ${id}: ${e.message}`);
-          throw e;
-        }
+      await program.methods
+        .buyTokens(
+          new BN(marketId),
+          [binIndex],
+          [amount],
+          new BN(1_000_000_000_000)
+        )
+        .accounts({
+          user: user.publicKey,
+          userTokenAccount: tokenAccount,
+          vault,
+        })
+        .signers([user])
+        .rpc();
+    },
+    // Token balance of a token account
+    balance: async (tokenAccount: anchor.web3.PublicKey) => {
+      const info = await provider.connection.getTokenAccountBalance(
+        tokenAccount
+      );
+      return new BN(info.value.amount);
+    },
+    replenishTokens,
+    closeMarket: async (marketId: number, winningBin: number = 0) => {
+      // Markets resolve independently; only close_ts must have passed
+      const [marketToClose] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), new BN(marketId).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const marketInfo = await program.account.market.fetch(marketToClose);
+      await waitUntil(marketInfo.closeTs.toNumber());
+
+      console.log(`Closing market ID ${marketId}...`);
+      await program.methods
+        .proposeResolution(new BN(marketId), winningBin)
+        .accounts({
+          authority: admin.publicKey,
+        })
+        .rpc();
+
+      // Finalize once the dispute window has passed
+      const proposed = await program.account.market.fetch(marketToClose);
+      if (proposed.disputeWindow.toNumber() > 0) {
+        await waitUntil(proposed.challengeDeadline.toNumber());
       }
+      await program.methods
+        .finalizeResolution(new BN(marketId))
+        .accounts({
+          finalizer: admin.publicKey,
+          seriesPrevious: null,
+        })
+        .rpc();
+      console.log(`Market ID ${marketId} closed successfully.`);
     },
   };
 
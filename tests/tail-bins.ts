@@ -0,0 +1,114 @@
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { setupTestEnvironment, TestEnv, waitUntil } from "./setup";
+
+describe("Tail Bins", () => {
+  let env: TestEnv;
+
+  // Ticks -360..=360 on a grid of 60 are bins 0..=12
+  const UNDERFLOW_BIN = 13;
+  const OVERFLOW_BIN = 14;
+
+  before(async () => {
+    env = await setupTestEnvironment();
+  });
+
+  async function propose(marketId: number, tick: number) {
+    await env.program.methods
+      .proposeResolutionAtTick(new BN(marketId), new BN(tick))
+      .accounts({
+        authority: env.admin.publicKey,
+      })
+      .rpc();
+  }
+
+  it("Tail bins should be appended after the in-range bins", async () => {
+    const { market, marketId } = await env.createNewMarket({ tailBins: true });
+
+    const marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.tailBins).to.be.true;
+    expect(marketInfo.binCount).to.equal(15);
+    expect((await env.getMarketBins(marketId)).length).to.equal(15);
+  });
+
+  it("Tail bins should be purchasable and win out-of-range outcomes", async () => {
+    const { market, marketId, vault, closeTs } = await env.createNewMarket({
+      tailBins: true,
+    });
+
+    await env.program.methods
+      .buyTokens(
+        new BN(marketId),
+        [UNDERFLOW_BIN, OVERFLOW_BIN],
+        [new BN(50_000_000_000), new BN(100_000_000_000)],
+        new BN(1_000_000_000_000)
+      )
+      .accounts({
+        user: env.user1.publicKey,
+        userTokenAccount: env.userTokenAccounts.user1,
+        vault,
+      })
+      .signers([env.user1])
+      .rpc();
+
+    const bought = await env.getMarketBins(marketId);
+    expect(bought[UNDERFLOW_BIN].toString()).to.equal("50000000000");
+    expect(bought[OVERFLOW_BIN].toString()).to.equal("100000000000");
+
+    await waitUntil(closeTs);
+
+    // Outcome above max_tick, on or off the grid
+    await propose(marketId, 1_000_000 + 7);
+    const proposed = await env.program.account.market.fetch(market);
+    expect(proposed.proposedBin).to.equal(OVERFLOW_BIN);
+
+    await env.program.methods
+      .finalizeResolution(new BN(marketId))
+      .accounts({
+        finalizer: env.admin.publicKey,
+        seriesPrevious: null,
+      })
+      .rpc();
+
+    const resolved = await env.program.account.market.fetch(market);
+    expect(resolved.winningBin).to.equal(OVERFLOW_BIN);
+    expect(resolved.winningSupply.toString()).to.equal("100000000000");
+  });
+
+  it("Outcomes below min_tick should resolve to the underflow bin", async () => {
+    const { market, marketId, closeTs } = await env.createNewMarket({
+      tailBins: true,
+    });
+    await waitUntil(closeTs);
+
+    await propose(marketId, -361);
+
+    const marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.proposedBin).to.equal(UNDERFLOW_BIN);
+  });
+
+  it("Outcomes just above max_tick should resolve to the last in-range bin", async () => {
+    const { market, marketId, closeTs } = await env.createNewMarket({
+      tailBins: true,
+    });
+    await waitUntil(closeTs);
+
+    // Bin 12 covers [360, 420); the overflow bin starts at max_tick + tick_spacing
+    await propose(marketId, 400);
+
+    const marketInfo = await env.program.account.market.fetch(market);
+    expect(marketInfo.proposedBin).to.equal(12);
+  });
+
+  it("Out-of-range outcomes should fail without tail bins", async () => {
+    const { marketId, closeTs } = await env.createNewMarket();
+    await waitUntil(closeTs);
+
+    try {
+      await propose(marketId, 420);
+      expect.fail("Out-of-range tick should fail without tail bins");
+    } catch (e) {
+      expect(e.toString()).to.include("Tick is outside the market range");
+    }
+  });
+});
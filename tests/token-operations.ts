@@ -47,6 +47,7 @@ describe("Token Operations", () => {
       const marketInfoAfter = await env.program.account.market.fetch(
         env.market
       );
+      const binsAfter = await env.getMarketBins(env.marketId);
       const finalTTotal = marketInfoAfter.tTotal;
       const finalCollateral = marketInfoAfter.collateralBalance;
 
@@ -66,9 +67,13 @@ describe("Token Operations", () => {
       expect(userPositionInfo.bins[0].amount.toString()).to.equal(
         amount.toString()
       );
+      expect(userPositionInfo.bins[0].paid.toString()).to.equal(
+        finalCollateral.sub(initialCollateral).toString()
+      );
+      expect(userPositionInfo.bins[0].received.toString()).to.equal("0");
 
       // Check bin quantity
-      expect(marketInfoAfter.bins[binIndex].toString()).to.equal(
+      expect(binsAfter[binIndex].toString()).to.equal(
         amount.toString()
       );
     });
@@ -108,6 +113,7 @@ describe("Token Operations", () => {
       const marketInfoAfter = await env.program.account.market.fetch(
         env.market
       );
+      const binsAfter = await env.getMarketBins(env.marketId);
       const finalTTotal = marketInfoAfter.tTotal;
       const finalCollateral = marketInfoAfter.collateralBalance;
 
@@ -138,7 +144,7 @@ describe("Token Operations", () => {
         expect(userBin.amount.toString()).to.equal(binAmount.toString());
 
         // Check bin quantity in market
-        expect(marketInfoAfter.bins[binIndex].toString()).to.equal(
+        expect(binsAfter[binIndex].toString()).to.equal(
           binAmount.toString()
         );
       }
@@ -149,7 +155,7 @@ describe("Token Operations", () => {
       await env.program.methods
         .activateMarket(new BN(env.marketId), false)
         .accounts({
-          owner: env.admin.publicKey,
+          authority: env.admin.publicKey,
         })
         .rpc();
 
@@ -178,7 +184,7 @@ describe("Token Operations", () => {
 
     it("Purchase attempt in a closed market should fail", async () => {
       // Close market sequentially
-      await env.closeMarketsSequentially(env.marketId, 0);
+      await env.closeMarket(env.marketId, 0);
 
       // Attempt to buy
       try {
@@ -230,6 +236,29 @@ describe("Token Operations", () => {
       }
     });
 
+    it("Purchase into a vault other than the market's should fail", async () => {
+      try {
+        await env.program.methods
+          .buyTokens(
+            new BN(env.marketId),
+            [0],
+            [new BN(100_000_000_000)],
+            new BN(150_000_000_000)
+          )
+          .accounts({
+            user: env.user1.publicKey,
+            userTokenAccount: env.userTokenAccounts.user1,
+            vault: env.userTokenAccounts.user2, // Same mint, wrong account
+          })
+          .signers([env.user1])
+          .rpc();
+
+        expect.fail("Purchase into the wrong vault should fail");
+      } catch (e) {
+        expect(e.toString()).to.include("Invalid vault account");
+      }
+    });
+
     it("Zero quantity purchase should ignore the bin", async () => {
       const binIndices = [0, 1];
       const amounts = [new BN(100_000_000_000), new BN(0)]; // Second bin is 0 quantity
@@ -261,10 +290,11 @@ describe("Token Operations", () => {
 
       // Check market info
       const marketInfo = await env.program.account.market.fetch(env.market);
+      const marketBins = await env.getMarketBins(env.marketId);
 
       // Only bin 0 should be updated
-      expect(marketInfo.bins[0].toString()).to.equal(amounts[0].toString());
-      expect(marketInfo.bins[1].toString()).to.equal("0"); // Quantity should be set to 0
+      expect(marketBins[0].toString()).to.equal(amounts[0].toString());
+      expect(marketBins[1].toString()).to.equal("0"); // Quantity should be set to 0
 
       // Total supply should only include actual purchased quantity
       expect(marketInfo.tTotal.toString()).to.equal(amounts[0].toString());
@@ -328,12 +358,446 @@ describe("Token Operations", () => {
 
       // Check market info
       const marketInfo = await env.program.account.market.fetch(env.market);
-      expect(marketInfo.bins[binIndex].toString()).to.equal(
+      const marketBins = await env.getMarketBins(env.marketId);
+      expect(marketBins[binIndex].toString()).to.equal(
         initialAmount.add(additionalAmount).toString()
       );
     });
   });
 
+  describe("Range Purchase (buyRange)", () => {
+    it("User should be able to buy every bin in a tick range", async () => {
+      const amount = new BN(10_000_000_000);
+
+      // Ticks -60, 0 and 60 are bins 5, 6 and 7 (min_tick -360, spacing 60)
+      await env.program.methods
+        .buyRange(
+          new BN(env.marketId),
+          new BN(-60),
+          new BN(60),
+          amount,
+          new BN(150_000_000_000)
+        )
+        .accounts({
+          user: env.user1.publicKey,
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault: env.vault,
+        })
+        .signers([env.user1])
+        .rpc();
+
+      const marketInfo = await env.program.account.market.fetch(env.market);
+      const marketBins = await env.getMarketBins(env.marketId);
+      for (const binIndex of [5, 6, 7]) {
+        expect(marketBins[binIndex].toString()).to.equal(
+          amount.toString()
+        );
+      }
+      expect(marketBins[4].toString()).to.equal("0");
+      expect(marketBins[8].toString()).to.equal("0");
+
+      const userPositionInfo =
+        await env.program.account.userMarketPosition.fetch(
+          await env.getUserPosition(env.user1, env.marketId)
+        );
+      expect(userPositionInfo.bins.map((b) => b.index)).to.deep.equal([
+        5, 6, 7,
+      ]);
+    });
+
+    it("Ticks off the spacing grid or outside the market should fail", async () => {
+      const cases = [
+        {
+          lower: -30,
+          upper: 60,
+          error: "Tick is not on the tick spacing grid",
+        },
+        { lower: -60, upper: 420, error: "Tick is outside the market range" },
+        {
+          lower: 60,
+          upper: -60,
+          error: "Lower tick must not be greater than upper tick",
+        },
+      ];
+
+      for (const { lower, upper, error } of cases) {
+        try {
+          await env.program.methods
+            .buyRange(
+              new BN(env.marketId),
+              new BN(lower),
+              new BN(upper),
+              new BN(10_000_000_000),
+              new BN(150_000_000_000)
+            )
+            .accounts({
+              user: env.user1.publicKey,
+              userTokenAccount: env.userTokenAccounts.user1,
+              vault: env.vault,
+            })
+            .signers([env.user1])
+            .rpc();
+
+          expect.fail(`Range ${lower}..${upper} should fail`);
+        } catch (e) {
+          expect(e.toString()).to.include(error);
+        }
+      }
+    });
+  });
+
+  describe("Budget Purchase (buyWithBudget)", () => {
+    it("User should receive equal quantities in each bin within the budget", async () => {
+      const binIndices = [0, 1, 2];
+      const budget = new BN(150_000_000_000);
+
+      const userPosition = await env.getUserPosition(env.user1, env.marketId);
+
+      await env.program.methods
+        .buyWithBudget(new BN(env.marketId), binIndices, budget, new BN(1))
+        .accounts({
+          user: env.user1.publicKey,
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault: env.vault,
+        })
+        .signers([env.user1])
+        .rpc();
+
+      const marketInfo = await env.program.account.market.fetch(env.market);
+      const marketBins = await env.getMarketBins(env.marketId);
+      const userPositionInfo =
+        await env.program.account.userMarketPosition.fetch(userPosition);
+
+      // Spent collateral must not exceed the budget
+      expect(marketInfo.collateralBalance.lte(budget)).to.be.true;
+
+      // Every bin should hold the same quantity
+      expect(userPositionInfo.bins.length).to.equal(binIndices.length);
+      const perBin = userPositionInfo.bins[0].amount;
+      expect(perBin.gt(new BN(0))).to.be.true;
+      for (const bin of userPositionInfo.bins) {
+        expect(bin.amount.toString()).to.equal(perBin.toString());
+        expect(marketBins[bin.index].toString()).to.equal(
+          perBin.toString()
+        );
+      }
+      expect(marketInfo.tTotal.toString()).to.equal(
+        perBin.mul(new BN(binIndices.length)).toString()
+      );
+    });
+
+    it("Purchase below the minimum token quantity should fail", async () => {
+      const budget = new BN(10_000_000_000);
+
+      try {
+        await env.program.methods
+          .buyWithBudget(
+            new BN(env.marketId),
+            [0, 1],
+            budget,
+            budget // Cannot get this many tokens per bin with the budget
+          )
+          .accounts({
+            user: env.user1.publicKey,
+            userTokenAccount: env.userTokenAccounts.user1,
+            vault: env.vault,
+          })
+          .signers([env.user1])
+          .rpc();
+
+        expect.fail("Purchase below minimum token quantity should fail");
+      } catch (e) {
+        expect(e.toString()).to.include(
+          "Purchasable token quantity is below minimum"
+        );
+      }
+    });
+  });
+
+  describe("Token Sale (sellTokens)", () => {
+    it("User should be able to sell part of a position back to the market", async () => {
+      const binIndices = [0, 1];
+      const amounts = [new BN(100_000_000_000), new BN(50_000_000_000)];
+      const sellAmount = new BN(40_000_000_000);
+
+      const userPosition = await env.getUserPosition(env.user1, env.marketId);
+
+      // Buy tokens first
+      await env.program.methods
+        .buyTokens(
+          new BN(env.marketId),
+          binIndices,
+          amounts,
+          new BN(200_000_000_000)
+        )
+        .accounts({
+          user: env.user1.publicKey,
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault: env.vault,
+        })
+        .signers([env.user1])
+        .rpc();
+
+      const marketInfoBefore = await env.program.account.market.fetch(
+        env.market
+      );
+      const userBalanceBefore = (
+        await env.provider.connection.getTokenAccountBalance(
+          env.userTokenAccounts.user1
+        )
+      ).value.amount;
+
+      // Sell part of bin 0
+      await env.program.methods
+        .sellTokens(new BN(env.marketId), [0], [sellAmount], new BN(0))
+        .accounts({
+          user: env.user1.publicKey,
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault: env.vault,
+        })
+        .signers([env.user1])
+        .rpc();
+
+      const marketInfoAfter = await env.program.account.market.fetch(
+        env.market
+      );
+      const binsAfter = await env.getMarketBins(env.marketId);
+      const userBalanceAfter = (
+        await env.provider.connection.getTokenAccountBalance(
+          env.userTokenAccounts.user1
+        )
+      ).value.amount;
+
+      // Market supply and bin quantity should decrease
+      expect(marketInfoAfter.tTotal.toString()).to.equal(
+        marketInfoBefore.tTotal.sub(sellAmount).toString()
+      );
+      expect(binsAfter[0].toString()).to.equal(
+        amounts[0].sub(sellAmount).toString()
+      );
+
+      // Collateral paid out should match the user's balance increase
+      const revenue = new BN(userBalanceAfter).sub(new BN(userBalanceBefore));
+      expect(revenue.gt(new BN(0))).to.be.true;
+      expect(marketInfoAfter.collateralBalance.toString()).to.equal(
+        marketInfoBefore.collateralBalance.sub(revenue).toString()
+      );
+
+      // User position should be debited
+      const userPositionInfo =
+        await env.program.account.userMarketPosition.fetch(userPosition);
+      const userBin0 = userPositionInfo.bins.find((bin) => bin.index === 0);
+      expect(userBin0.amount.toString()).to.equal(
+        amounts[0].sub(sellAmount).toString()
+      );
+
+      // Revenue should be recorded against the sold bin
+      expect(userBin0.paid.gt(new BN(0))).to.be.true;
+      expect(userBin0.received.toString()).to.equal(revenue.toString());
+    });
+
+    it("Position account should grow with new bins and shrink when sold out", async () => {
+      // 8-byte discriminator + owner, market_id, cost_basis, closed_paid,
+      // closed_received, vec length + 26 bytes per bin
+      const positionSize = (bins: number) => 76 + 26 * bins;
+      const userPosition = await env.getUserPosition(env.user1, env.marketId);
+
+      const buy = (bins: number[]) =>
+        env.program.methods
+          .buyTokens(
+            new BN(env.marketId),
+            bins,
+            bins.map(() => new BN(10_000_000_000)),
+            new BN(100_000_000_000)
+          )
+          .accounts({
+            user: env.user1.publicKey,
+            userTokenAccount: env.userTokenAccounts.user1,
+            vault: env.vault,
+          })
+          .signers([env.user1])
+          .rpc();
+
+      await buy([0]);
+      let info = await env.provider.connection.getAccountInfo(userPosition);
+      expect(info.data.length).to.equal(positionSize(1));
+
+      await buy([1, 2]);
+      info = await env.provider.connection.getAccountInfo(userPosition);
+      expect(info.data.length).to.equal(positionSize(3));
+      const lamportsBefore = info.lamports;
+      const positionBefore =
+        await env.program.account.userMarketPosition.fetch(userPosition);
+      const paidSoldOut = positionBefore.bins
+        .filter((bin) => bin.index !== 2)
+        .reduce((sum, bin) => sum.add(bin.paid), new BN(0));
+      const balanceBefore = (
+        await env.provider.connection.getTokenAccountBalance(
+          env.userTokenAccounts.user1
+        )
+      ).value.amount;
+
+      // Selling bins 0 and 1 out compacts them away and refunds their rent
+      await env.program.methods
+        .sellTokens(
+          new BN(env.marketId),
+          [0, 1],
+          [new BN(10_000_000_000), new BN(10_000_000_000)],
+          new BN(0)
+        )
+        .accounts({
+          user: env.user1.publicKey,
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault: env.vault,
+        })
+        .signers([env.user1])
+        .rpc();
+
+      info = await env.provider.connection.getAccountInfo(userPosition);
+      expect(info.data.length).to.equal(positionSize(1));
+      expect(info.lamports).to.equal(
+        await env.provider.connection.getMinimumBalanceForRentExemption(
+          positionSize(1)
+        )
+      );
+      expect(info.lamports).to.be.lessThan(lamportsBefore);
+
+      const userPositionInfo =
+        await env.program.account.userMarketPosition.fetch(userPosition);
+      expect(userPositionInfo.bins.map((bin) => bin.index)).to.deep.equal([2]);
+
+      // The dropped bins' ledger is kept in the position totals
+      const balanceAfter = (
+        await env.provider.connection.getTokenAccountBalance(
+          env.userTokenAccounts.user1
+        )
+      ).value.amount;
+      expect(userPositionInfo.closedPaid.toString()).to.equal(
+        paidSoldOut.toString()
+      );
+      expect(userPositionInfo.closedReceived.toString()).to.equal(
+        new BN(balanceAfter).sub(new BN(balanceBefore)).toString()
+      );
+    });
+
+    it("Selling more than the owned quantity should fail", async () => {
+      const amount = new BN(100_000_000_000);
+
+      await env.program.methods
+        .buyTokens(new BN(env.marketId), [0], [amount], new BN(150_000_000_000))
+        .accounts({
+          user: env.user1.publicKey,
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault: env.vault,
+        })
+        .signers([env.user1])
+        .rpc();
+
+      // user2 also buys so the bin holds more than user1's position
+      await env.program.methods
+        .buyTokens(new BN(env.marketId), [0], [amount], new BN(150_000_000_000))
+        .accounts({
+          user: env.user2.publicKey,
+          userTokenAccount: env.userTokenAccounts.user2,
+          vault: env.vault,
+        })
+        .signers([env.user2])
+        .rpc();
+
+      try {
+        await env.program.methods
+          .sellTokens(
+            new BN(env.marketId),
+            [0],
+            [amount.add(new BN(10_000_000))],
+            new BN(0)
+          )
+          .accounts({
+            user: env.user1.publicKey,
+            userTokenAccount: env.userTokenAccounts.user1,
+            vault: env.vault,
+          })
+          .signers([env.user1])
+          .rpc();
+
+        expect.fail("Selling more than owned quantity should fail");
+      } catch (e) {
+        expect(e.toString()).to.include(
+          "Cannot sell more tokens than held in position"
+        );
+      }
+    });
+
+    it("Sale below the minimum collateral out should fail", async () => {
+      const amount = new BN(100_000_000_000);
+
+      await env.program.methods
+        .buyTokens(new BN(env.marketId), [0], [amount], new BN(150_000_000_000))
+        .accounts({
+          user: env.user1.publicKey,
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault: env.vault,
+        })
+        .signers([env.user1])
+        .rpc();
+
+      try {
+        await env.program.methods
+          .sellTokens(
+            new BN(env.marketId),
+            [0],
+            [new BN(50_000_000_000)],
+            new BN(150_000_000_000) // More than the sale can return
+          )
+          .accounts({
+            user: env.user1.publicKey,
+            userTokenAccount: env.userTokenAccounts.user1,
+            vault: env.vault,
+          })
+          .signers([env.user1])
+          .rpc();
+
+        expect.fail("Sale below minimum collateral out should fail");
+      } catch (e) {
+        expect(e.toString()).to.include(
+          "Revenue is below minimum collateral out"
+        );
+      }
+    });
+
+    it("Sale attempt in a closed market should fail", async () => {
+      const amount = new BN(100_000_000_000);
+
+      await env.program.methods
+        .buyTokens(new BN(env.marketId), [0], [amount], new BN(150_000_000_000))
+        .accounts({
+          user: env.user1.publicKey,
+          userTokenAccount: env.userTokenAccounts.user1,
+          vault: env.vault,
+        })
+        .signers([env.user1])
+        .rpc();
+
+      await env.closeMarket(env.marketId, 0);
+
+      try {
+        await env.program.methods
+          .sellTokens(new BN(env.marketId), [0], [amount], new BN(0))
+          .accounts({
+            user: env.user1.publicKey,
+            userTokenAccount: env.userTokenAccounts.user1,
+            vault: env.vault,
+          })
+          .signers([env.user1])
+          .rpc();
+
+        expect.fail("Sale in closed market should fail");
+      } catch (e) {
+        expect(e.toString()).to.include("Market is closed");
+      }
+    });
+  });
+
   describe("Position Transfer (transferPosition)", () => {
     it("User should be able to transfer position to another wallet", async () => {
       // First, create user1 position
@@ -400,12 +864,28 @@ describe("Token Operations", () => {
         transferAmount.toString()
       );
 
+      // Half of bin 0's paid collateral should move with the tokens
+      const user2Bin0 = user2PositionAfter.bins[0];
+      expect(user2Bin0.paid.toString()).to.equal(
+        user1Bin0.paid.add(user2Bin0.paid).div(new BN(2)).toString()
+      );
+      expect(user2Bin0.received.toString()).to.equal("0");
+
+      // Cost basis follows the per-bin ledger of the transferred tokens
+      expect(user2PositionAfter.costBasis.toString()).to.equal(
+        user2Bin0.paid.toString()
+      );
+      expect(user1PositionAfter.costBasis.toString()).to.equal(
+        user1Bin0.paid.add(user1Bin1.paid).toString()
+      );
+
       // Market total quantity should remain unchanged
       const marketInfo = await env.program.account.market.fetch(env.market);
-      expect(marketInfo.bins[binIndices[0]].toString()).to.equal(
+      const marketBins = await env.getMarketBins(env.marketId);
+      expect(marketBins[binIndices[0]].toString()).to.equal(
         amounts[0].toString()
       );
-      expect(marketInfo.bins[binIndices[1]].toString()).to.equal(
+      expect(marketBins[binIndices[1]].toString()).to.equal(
         amounts[1].toString()
       );
     });